@@ -0,0 +1,82 @@
+//! Generates the solution registry of the main binary.
+//!
+//! Every solution in `src/bin/{year}-{day}.rs` is included as a module of the main binary so
+//! `cargo all` and `cargo time` can call its parts directly instead of spawning `cargo run`.
+//! Also exposes the compiler version as `AOC_RUSTC_VERSION` for the benchmark history.
+use std::{env, fmt::Write, fs, path::Path, process::Command};
+
+const SOLUTION_TYPE: &str = "advent_of_code::template::registry::Solution";
+
+fn is_solution_name(name: &str) -> bool {
+    let bytes = name.as_bytes();
+    bytes.len() == 7
+        && bytes[4] == b'-'
+        && bytes
+            .iter()
+            .enumerate()
+            .all(|(i, b)| i == 4 || b.is_ascii_digit())
+}
+
+fn module_name(name: &str) -> String {
+    format!("solution_{}", name.replace('-', "_"))
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    let mut names: Vec<String> = fs::read_dir(Path::new(&manifest_dir).join("src/bin"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    let name = path.file_stem()?.to_str()?.to_string();
+                    is_solution_name(&name).then_some(name)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+
+    let mut registry = String::new();
+
+    for name in &names {
+        let path = Path::new(&manifest_dir)
+            .join("src/bin")
+            .join(format!("{name}.rs"));
+        writeln!(registry, "#[cfg(not(test))]").unwrap();
+        writeln!(registry, "#[allow(dead_code)]").unwrap();
+        writeln!(registry, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(registry, "mod {};\n", module_name(name)).unwrap();
+    }
+
+    writeln!(registry, "#[cfg(not(test))]").unwrap();
+    writeln!(registry, "pub static SOLUTIONS: &[{SOLUTION_TYPE}] = &[").unwrap();
+    for name in &names {
+        writeln!(registry, "    {}::SOLUTION,", module_name(name)).unwrap();
+    }
+    writeln!(registry, "];\n").unwrap();
+
+    // solutions are tested as part of their own binaries.
+    writeln!(registry, "#[cfg(test)]").unwrap();
+    writeln!(registry, "pub static SOLUTIONS: &[{SOLUTION_TYPE}] = &[];").unwrap();
+
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+
+    // the benchmark history records which compiler built the benched solutions.
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=AOC_RUSTC_VERSION={}", version.trim());
+}
@@ -0,0 +1,107 @@
+advent_of_code::solution!(2024, 1);
+
+use std::{error::Error, fmt::Display, num::ParseIntError};
+
+const COMPLEXITY: Option<&str> = Some("O(n log n), sorts both lists");
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    MissingNumber { line: usize },
+    InvalidNumber { line: usize, source: ParseIntError },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingNumber { line } => {
+                write!(f, "line {line} has less than two numbers")
+            }
+            ParseError::InvalidNumber { line, .. } => {
+                write!(f, "line {line} has an invalid number")
+            }
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::MissingNumber { .. } => None,
+            ParseError::InvalidNumber { source, .. } => Some(source),
+        }
+    }
+}
+
+fn parse_lists(input: &str) -> Result<(Vec<usize>, Vec<usize>), ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let line_number = i + 1;
+            let mut parts = line.split_whitespace().map(|part| {
+                part.parse::<usize>()
+                    .map_err(|source| ParseError::InvalidNumber {
+                        line: line_number,
+                        source,
+                    })
+            });
+            let missing = || ParseError::MissingNumber { line: line_number };
+            let a = parts.next().ok_or_else(missing)??;
+            let b = parts.next().ok_or_else(missing)??;
+            Ok((a, b))
+        })
+        .collect()
+}
+
+pub fn part_one(input: &str) -> Result<usize, ParseError> {
+    let mut lists = parse_lists(input)?;
+    lists.0.sort_unstable();
+    lists.1.sort_unstable();
+    let total_distance = lists
+        .0
+        .into_iter()
+        .zip(lists.1)
+        .map(|(a, b)| a.abs_diff(b))
+        .sum();
+    Ok(total_distance)
+}
+
+pub fn part_two(input: &str) -> Result<usize, ParseError> {
+    let lists = parse_lists(input)?;
+
+    let mut similarity_score = 0;
+    for a in lists.0 {
+        similarity_score += a * lists.1.iter().filter(|&b| a == *b).count();
+    }
+
+    Ok(similarity_score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(11));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(31));
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(
+            part_one("1   2\n3"),
+            Err(ParseError::MissingNumber { line: 2 })
+        );
+        assert!(matches!(
+            part_two("1   x"),
+            Err(ParseError::InvalidNumber { line: 1, .. })
+        ));
+    }
+}
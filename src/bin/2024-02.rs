@@ -1,4 +1,4 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2024, 2);
 
 fn load_input(input: &str) -> Vec<Vec<usize>> {
     input
@@ -37,7 +37,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(res)
 }
 
-fn determine_ordering(inp: &Vec<impl Ord>) -> core::cmp::Ordering {
+fn determine_ordering(inp: &[impl Ord]) -> core::cmp::Ordering {
     // pick 3 subject pairs, compare them and return the ordering that appears at least twice
     let mut orderings = Vec::new();
     for (i, a) in inp.iter().enumerate() {
@@ -90,7 +90,7 @@ pub fn part_two(input: &str) -> Option<u32> {
             for window in levels
                 .iter()
                 .enumerate()
-                .filter_map(|(n, el)| (i != n).then(|| el))
+                .filter_map(|(n, el)| (i != n).then_some(el))
                 .collect::<Vec<_>>()
                 .windows(2)
             {
@@ -121,13 +121,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4));
     }
 }
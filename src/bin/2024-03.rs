@@ -1,4 +1,4 @@
-advent_of_code::solution!(3);
+advent_of_code::solution!(2024, 3);
 
 use regex::Regex;
 use std::sync::LazyLock;
@@ -71,19 +71,15 @@ impl<'a> Iterator for Scanner<'a> {
     type Item = Instruction;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.gen.next() {
-            Some(caps) => Some(Instruction::from(&caps)),
-            None => None,
-        }
+        self.gen.next().map(|caps| Instruction::from(&caps))
     }
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut res = 0;
     for instruc in Scanner::new(input) {
-        match InstructionInterpreter::interpret(&instruc) {
-            InstructionResult::Mul(val) => res += val,
-            _ => (),
+        if let InstructionResult::Mul(val) = InstructionInterpreter::interpret(&instruc) {
+            res += val
         }
     }
     Some(res)
@@ -110,14 +106,14 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(161));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(48));
     }
@@ -0,0 +1,110 @@
+advent_of_code::solution!(2024, 4);
+
+use advent_of_code::grid::{Direction, Grid, Point};
+
+fn occurs_in_dir(
+    grid: &Grid<char>,
+    origin: Option<Point>,
+    word: &str,
+    direction: Direction,
+) -> bool {
+    origin.is_some_and(|origin| {
+        grid.ray(origin, direction)
+            .map(|(_, c)| *c)
+            .take(word.len())
+            .eq(word.chars())
+    })
+}
+
+fn occurences(grid: &Grid<char>, word: &str) -> u32 {
+    let mut count = 0;
+    for point in grid.points() {
+        // Check all directions
+        for direction in Direction::ALL {
+            if occurs_in_dir(grid, Some(point), word, direction) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+fn count_x(grid: &Grid<char>, word: &str) -> u32 {
+    let mut count = 0;
+    let shift = word.len() as isize - 1;
+
+    for point in grid.points() {
+        // Check all directions
+        // right down
+        if occurs_in_dir(grid, Some(point), word, Direction::DownRight) {
+            // shifted right, left down
+            if occurs_in_dir(
+                grid,
+                point.checked_offset(shift, 0),
+                word,
+                Direction::DownLeft,
+            ) {
+                count += 1;
+            }
+            // shifted down, right up
+            if occurs_in_dir(
+                grid,
+                point.checked_offset(0, shift),
+                word,
+                Direction::UpRight,
+            ) {
+                count += 1;
+            }
+        }
+        // left up
+        if occurs_in_dir(grid, Some(point), word, Direction::UpLeft) {
+            // shifted left, right up
+            if occurs_in_dir(
+                grid,
+                point.checked_offset(-shift, 0),
+                word,
+                Direction::UpRight,
+            ) {
+                count += 1;
+            }
+            // shifted up, left down
+            if occurs_in_dir(
+                grid,
+                point.checked_offset(0, -shift),
+                word,
+                Direction::DownLeft,
+            ) {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().ok()?;
+    Some(occurences(&grid, "XMAS"))
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().ok()?;
+    Some(count_x(&grid, "MAS"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(18));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(9));
+    }
+}
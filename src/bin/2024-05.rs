@@ -1,38 +1,25 @@
-use std::ops::Index;
+advent_of_code::solution!(2024, 5, parse_input -> Input);
 
-advent_of_code::solution!(5);
+use advent_of_code::parse::{self, ParseError};
 
-fn parse_input(input: &str) -> (Vec<(u32, u32)>, Vec<Vec<u32>>) {
-    match input
-        .split("\n\n")
-        .map(|block| block.lines().collect::<Vec<&str>>())
-        .collect::<Vec<Vec<&str>>>()
-        .as_slice()
-    {
-        [orderings, updates] => (
-            orderings
-                .iter()
-                .map(
-                    |line| match line.split("|").collect::<Vec<&str>>().as_slice() {
-                        [page_number_x, page_number_y] => (
-                            page_number_x.parse().unwrap(),
-                            page_number_y.parse().unwrap(),
-                        ),
-                        _ => panic!("Invalid input"),
-                    },
-                )
-                .collect(),
-            updates
-                .iter()
-                .map(|line| line.split(",").map(|x| x.parse().unwrap()).collect())
-                .collect(),
-        ),
-        _ => panic!("Invalid input"),
-    }
+type Input = (Vec<(u32, u32)>, Vec<Vec<u32>>);
+
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    let [orderings, updates] = parse::exact_blocks(input)?;
+    Ok((
+        orderings
+            .iter()
+            .map(|line| line.pair("|"))
+            .collect::<Result<_, _>>()?,
+        updates
+            .iter()
+            .map(|line| line.integers())
+            .collect::<Result<_, _>>()?,
+    ))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (orderings, updates) = parse_input(input);
+pub fn part_one(input: &Input) -> Option<u32> {
+    let (orderings, updates) = input;
     let mut res = 0;
     for update in updates {
         let mut is_correct = true;
@@ -62,8 +49,8 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(res)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (orderings, updates) = parse_input(input);
+pub fn part_two(input: &Input) -> Option<u32> {
+    let (orderings, updates) = input;
     let incorrect_updates = updates
         .iter()
         .filter(|update| {
@@ -137,13 +124,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse_input(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_one(&input.unwrap());
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse_input(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_two(&input.unwrap());
         assert_eq!(result, Some(123));
     }
 }
@@ -0,0 +1,122 @@
+advent_of_code::solution!(2024, 6);
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use advent_of_code::grid::{Direction, Grid, Point};
+use advent_of_code::simulate::find_cycle;
+use advent_of_code::template::bench::BenchSettings;
+use advent_of_code::visualize::{self, Cell, Color, Frame};
+
+/// Part two simulates the guard once for every candidate obstruction, which is too slow to
+/// collect a stable number of samples within the default budget.
+const BENCH: BenchSettings = BenchSettings {
+    budget: Some(Duration::from_secs(5)),
+    min_samples: Some(20),
+    ..BenchSettings::NONE
+};
+
+const COMPLEXITY: Option<&str> = Some("O(w·h·s), simulates the guard for every free tile");
+
+/// The position of the guard and the direction they are facing.
+type Guard = (Point, Direction);
+
+#[derive(Debug, Clone)]
+struct Lab {
+    guard: Guard,
+    /// Whether a tile is occupied by an obstruction.
+    map: Grid<bool>,
+}
+
+impl Lab {
+    /// Moves the guard a single step or turns them right if they are facing an obstruction.
+    /// Returns `None` once the guard exits the map.
+    fn tick(&self, (position, orientation): &Guard) -> Option<Guard> {
+        let new_pos = self.map.step(*position, *orientation)?;
+        if self.map[new_pos] {
+            Some((*position, orientation.rotate_right()))
+        } else {
+            Some((new_pos, *orientation))
+        }
+    }
+
+    fn is_loop(&self) -> bool {
+        find_cycle(self.guard, |guard| self.tick(guard)).is_some()
+    }
+
+    /// Draws the map with the guard at `guard` and the tiles they visited so far.
+    fn frame(&self, (position, orientation): Guard, visited: &HashSet<Point>) -> Frame {
+        Frame::from_grid(&self.map, |point, occupied| {
+            if point == position {
+                Cell::new(orientation.to_arrow().unwrap()).color(Color::Red)
+            } else if *occupied {
+                Cell::new('#').color(Color::Gray)
+            } else if visited.contains(&point) {
+                Cell::new('X').color(Color::Yellow)
+            } else {
+                Cell::new('.')
+            }
+        })
+    }
+}
+
+fn parse_input(input: &str) -> Lab {
+    let chars: Grid<char> = input.parse().expect("Map is not rectangular");
+
+    let position = chars
+        .find(|c| Direction::from_arrow(*c).is_some())
+        .expect("No guard found in input");
+    let guard = (position, Direction::from_arrow(chars[position]).unwrap());
+
+    let map = chars.map(|c| match c {
+        '#' => true,
+        '.' | '^' | 'v' | '<' | '>' => false,
+        _ => panic!("Invalid character in input"),
+    });
+
+    Lab { guard, map }
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let lab = parse_input(input);
+    let mut guarded: HashSet<Point> = HashSet::new();
+    for guard in std::iter::successors(Some(lab.guard), |guard| lab.tick(guard)) {
+        guarded.insert(guard.0);
+        visualize::push_with(|| lab.frame(guard, &guarded));
+    }
+    Some(guarded.len() as u32)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let lab = parse_input(input);
+    let mut result = 0;
+    for point in lab.map.positions(|occupied| !occupied) {
+        if point == lab.guard.0 {
+            continue;
+        }
+        let mut new_lab = lab.clone();
+        new_lab.map[point] = true;
+        if new_lab.is_loop() {
+            result += 1;
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(41));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(6));
+    }
+}
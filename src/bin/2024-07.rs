@@ -1,32 +1,21 @@
-advent_of_code::solution!(7);
+advent_of_code::solution!(2024, 7, parse_input -> Input);
 
+use advent_of_code::parse::{self, ParseError};
 use itertools::Itertools;
 
+const COMPLEXITY: Option<&str> = Some("O(n·3ᵏ), tries every combination of k operators");
+
+type Input = Vec<(usize, Vec<usize>)>;
+
 const OPERATORS: [&str; 3] = ["+", "*", "||"];
 
-fn parse_input(input: &str) -> Vec<(usize, Vec<usize>)> {
-    input
-        .lines()
-        .map(
-            |line| match line.split(": ").collect::<Vec<&str>>().as_slice() {
-                [result, operands] => {
-                    let operands = operands
-                        .split(" ")
-                        .map(|operand| operand.parse().expect("Could not parse operand"))
-                        .collect::<Vec<usize>>();
-                    (result.parse().expect("Could not parse result"), operands)
-                }
-                _ => panic!("Could not parse line"),
-            },
-        )
-        .collect()
+fn parse_input(input: &str) -> Result<Input, ParseError> {
+    parse::lines(input).map(|line| line.record(":")).collect()
 }
 
 fn generate_combinations(chars: &[&str], length: usize) -> Vec<Vec<String>> {
     // Create an iterator that repeats the slice `chars` `length` times
-    std::iter::repeat(chars)
-        // Take only `length` number of repeated slices
-        .take(length)
+    std::iter::repeat_n(chars, length)
         // Generate the Cartesian product of the repeated slices
         .multi_cartesian_product()
         // Map each combination to a vector of strings
@@ -35,10 +24,9 @@ fn generate_combinations(chars: &[&str], length: usize) -> Vec<Vec<String>> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let input = parse_input(input);
+pub fn part_one(input: &Input) -> Option<usize> {
     let mut res = 0;
-    for (result, operands) in input {
+    for &(result, ref operands) in input {
         let mut can_be_solved = false;
         for combination in generate_combinations(&OPERATORS, operands.len() - 1) {
             let mut comb_result = *operands.first().expect("Could not get first operand");
@@ -62,10 +50,9 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(res)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let input = parse_input(input);
+pub fn part_two(input: &Input) -> Option<usize> {
     let mut res = 0;
-    for (result, operands) in input {
+    for &(result, ref operands) in input {
         let mut can_be_solved = false;
         for combination in generate_combinations(&OPERATORS, operands.len() - 1) {
             let mut comb_result = *operands.first().expect("Could not get first operand");
@@ -104,13 +91,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse_input(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_one(&input.unwrap());
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = parse_input(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_two(&input.unwrap());
         assert_eq!(result, Some(11387));
     }
 
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use advent_of_code::grid::{Grid, Point};
+
+advent_of_code::solution!(2024, 8);
+
+#[derive(Debug, PartialEq)]
+struct Spot {
+    position: Point,
+    antenna: Option<char>,
+}
+
+#[derive(Debug)]
+struct Map {
+    spots: Grid<Option<char>>,
+}
+
+impl Map {
+    fn new(input: &str) -> Self {
+        let spots = Grid::parse(input, |c| match c {
+            '.' => None,
+            _ => Some(c),
+        })
+        .expect("Map is not rectangular");
+
+        Self { spots }
+    }
+
+    fn get_spots_with_antennas(&self) -> Vec<Spot> {
+        self.spots
+            .iter()
+            .filter(|(_, antenna)| antenna.is_some())
+            .map(|(position, antenna)| Spot {
+                position,
+                antenna: *antenna,
+            })
+            .collect()
+    }
+
+    fn calc_antinode_location_part1(&self, antenna_a: &Spot, antenna_b: &Spot) -> Option<Point> {
+        let (distance_x, distance_y) = antenna_a.position.delta(antenna_b.position);
+
+        antenna_a
+            .position
+            .checked_offset(distance_x * 2, distance_y * 2)
+            // Antinode is outside the map
+            .filter(|antinode| self.spots.contains(*antinode))
+    }
+
+    fn locate_antinodes_part1(&self) -> Vec<Point> {
+        let mut antinodes = HashSet::<Point>::new();
+        let antennas = self.get_spots_with_antennas();
+
+        for antenna in &antennas {
+            let same_freq_antennas = antennas
+                .iter()
+                .filter(|a| a.antenna == antenna.antenna && a != &antenna)
+                .collect::<Vec<_>>();
+
+            for other_antenna in same_freq_antennas {
+                if let Some(antinode) = self.calc_antinode_location_part1(antenna, other_antenna) {
+                    antinodes.insert(antinode);
+                }
+            }
+        }
+
+        antinodes.into_iter().collect()
+    }
+
+    fn locate_antinodes_part2(&self) -> Vec<Point> {
+        let mut antinodes = HashSet::<Point>::new();
+        let antennas = self.get_spots_with_antennas();
+
+        for antenna in &antennas {
+            let same_freq_antennas = antennas
+                .iter()
+                .filter(|a| a.antenna == antenna.antenna && a != &antenna)
+                .collect::<Vec<_>>();
+
+            for other_antenna in same_freq_antennas {
+                antinodes.extend(self.calc_antinode_location_part2(antenna, other_antenna));
+            }
+        }
+
+        antinodes.into_iter().collect()
+    }
+
+    fn calc_antinode_location_part2(&self, antenna_a: &Spot, antenna_b: &Spot) -> Vec<Point> {
+        let (distance_x, distance_y) = antenna_a.position.delta(antenna_b.position);
+
+        std::iter::successors(Some(antenna_b.position), |antinode| {
+            antinode.checked_offset(distance_x, distance_y)
+        })
+        // Antinode is outside the map
+        .take_while(|antinode| self.spots.contains(*antinode))
+        .collect()
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let map = Map::new(input);
+    let antinodes = map.locate_antinodes_part1();
+    Some(antinodes.len())
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let map = Map::new(input);
+    let antinodes = map.locate_antinodes_part2();
+
+    Some(antinodes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(14));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(34));
+    }
+}
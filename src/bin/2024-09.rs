@@ -1,7 +1,19 @@
 use std::fmt;
-use std::iter::repeat;
+use std::time::Duration;
 
-advent_of_code::solution!(9);
+use advent_of_code::template::bench::BenchSettings;
+
+advent_of_code::solution!(2024, 9);
+
+/// Compacting whole files scans the disk for every file, so benching takes more time to
+/// collect enough samples.
+const BENCH: BenchSettings = BenchSettings {
+    budget: Some(Duration::from_secs(3)),
+    min_samples: Some(20),
+    ..BenchSettings::NONE
+};
+
+const COMPLEXITY: Option<&str> = Some("O(f·b), scans the disk for a gap for every file");
 
 #[derive(Clone, Debug)]
 struct Block {
@@ -45,31 +57,27 @@ impl DiskMap {
     fn compress_per_block(&self) -> DiskMap {
         let mut compressed = self.clone();
         // while there is any block that has file set to None between first and last file block
-        loop {
-            if let (Some((last_file_index, _)), Some((first_file_index, _))) = (
-                compressed
+        while let (Some((last_file_index, _)), Some((first_file_index, _))) = (
+            compressed
+                .blocks
+                .iter()
+                .enumerate()
+                .rev()
+                .find(|(_, block)| block.file.is_some()),
+            compressed
+                .blocks
+                .iter()
+                .enumerate()
+                .find(|(_, block)| block.file.is_some()),
+        ) {
+            if last_file_index > first_file_index {
+                if let Some(index_where_to_move) = compressed
                     .blocks
                     .iter()
-                    .enumerate()
-                    .rev()
-                    .find(|(_, block)| block.file.is_some()),
-                compressed
-                    .blocks
-                    .iter()
-                    .enumerate()
-                    .find(|(_, block)| block.file.is_some()),
-            ) {
-                if last_file_index > first_file_index {
-                    if let Some(index_where_to_move) = compressed
-                        .blocks
-                        .iter()
-                        .position(|block| block.file.is_none())
-                    {
-                        if index_where_to_move < last_file_index {
-                            compressed.blocks.swap(index_where_to_move, last_file_index);
-                        } else {
-                            break;
-                        }
+                    .position(|block| block.file.is_none())
+                {
+                    if index_where_to_move < last_file_index {
+                        compressed.blocks.swap(index_where_to_move, last_file_index);
                     } else {
                         break;
                     }
@@ -124,16 +132,16 @@ impl From<Vec<usize>> for DiskMap {
         for size in input.iter() {
             match alternating.next() {
                 Some(true) => {
-                    blocks.extend(
-                        repeat(Block {
+                    blocks.extend(std::iter::repeat_n(
+                        Block {
                             file: Some(file_id),
-                        })
-                        .take(*size),
-                    );
+                        },
+                        *size,
+                    ));
                     file_id += 1;
                 }
                 Some(false) => {
-                    blocks.extend(repeat(Block { file: None }).take(*size));
+                    blocks.extend(std::iter::repeat_n(Block { file: None }, *size));
                 }
                 None => panic!("Alternating iterator exhausted"),
             }
@@ -149,7 +157,7 @@ fn parse_input(input: &str) -> DiskMap {
             .enumerate()
             .map(|(i, c)| {
                 c.to_digit(10)
-                    .expect(format!("Invalid digit: {} at {}", c, i).as_str())
+                    .unwrap_or_else(|| panic!("Invalid digit: {} at {}", c, i))
                     as usize
             })
             .collect::<Vec<usize>>(),
@@ -193,13 +201,13 @@ mod tests {
     #[test]
     fn test_part_one() {
         println!("{:?}", part_one("123456789012"));
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1928));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(2858));
     }
 }
@@ -0,0 +1,68 @@
+use advent_of_code::grid::{Grid, Point};
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::search::{count_paths, flood_fill};
+
+advent_of_code::solution!(2024, 10, parse_input -> Grid<u32>);
+
+fn parse_input(input: &str) -> Result<Grid<u32>, ParseError> {
+    parse::digit_grid(input)
+}
+
+fn find_trailheads(map: &Grid<u32>) -> impl Iterator<Item = Point> + '_ {
+    let trailhead_value = 0;
+    map.positions(move |&spot| spot == trailhead_value)
+}
+
+/// Returns the locations a hiking trail can continue to, which are exactly one higher.
+fn trail_step(map: &Grid<u32>, current_location: Point) -> impl Iterator<Item = Point> + '_ {
+    let next_value = map[current_location] + 1;
+    map.neighbors4(current_location)
+        .filter(move |next_location| map[*next_location] == next_value)
+}
+
+pub fn part_one(map: &Grid<u32>) -> Option<usize> {
+    // for each trailhead, count number of unique final locations
+    let result = find_trailheads(map)
+        .map(|trailhead| {
+            flood_fill(trailhead, |location| trail_step(map, *location))
+                .into_iter()
+                .filter(|location| map[*location] == 9)
+                .count()
+        })
+        .sum();
+
+    Some(result)
+}
+
+pub fn part_two(map: &Grid<u32>) -> Option<usize> {
+    let result = find_trailheads(map)
+        .map(|trailhead| {
+            count_paths(
+                trailhead,
+                |location| trail_step(map, *location),
+                |location| map[*location] == 9,
+            )
+        })
+        .sum();
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = parse_input(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_one(&input.unwrap());
+        assert_eq!(result, Some(36));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = parse_input(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_two(&input.unwrap());
+        assert_eq!(result, Some(81));
+    }
+}
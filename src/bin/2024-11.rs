@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use advent_of_code::parse::{self, ParseError};
+use advent_of_code::simulate;
+
+advent_of_code::solution!(2024, 11, parse_input -> HashMap<usize, usize>);
+
+const COMPLEXITY: Option<&str> = Some("O(d·b), counts the distinct stones of every blink");
+
+type Rule = fn(usize) -> Option<Vec<usize>>;
+
+const RULES: &[Rule] = &[
+    // the stone is engraved with the number 0
+    |stone: usize| -> Option<Vec<usize>> {
+        if stone == 0 {
+            Some(vec![1])
+        } else {
+            None
+        }
+    },
+    // the stone is engraved with a number that has an even number of digits
+    |stone: usize| -> Option<Vec<usize>> {
+        let digits = stone.to_string();
+        if digits.len().is_multiple_of(2) {
+            let mid = digits.len() / 2;
+            let left = digits[..mid].parse().expect("Failed to parse left half");
+            let right = digits[mid..].parse().expect("Failed to parse right half");
+            let result = vec![left, right];
+            Some(result)
+        } else {
+            None
+        }
+    },
+    // none of the other rules apply
+    |stone: usize| -> Option<Vec<usize>> {
+        let result = vec![stone * 2024];
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    },
+];
+
+fn parse_input(input: &str) -> Result<HashMap<usize, usize>, ParseError> {
+    let mut map = HashMap::new();
+    for stone in parse::integers(input)? {
+        *map.entry(stone).or_insert(0) += 1;
+    }
+    Ok(map)
+}
+
+/// Applies the first matching rule to every stone.
+fn blink(stones: &HashMap<usize, usize>) -> HashMap<usize, usize> {
+    let mut new_stones = HashMap::new();
+    for (&stone, &count) in stones {
+        for rule in RULES {
+            if let Some(result) = rule(stone) {
+                for new_stone in result {
+                    *new_stones.entry(new_stone).or_insert(0) += count;
+                }
+                break;
+            }
+        }
+    }
+    new_stones
+}
+
+pub fn part_one(stones: &HashMap<usize, usize>) -> Option<usize> {
+    const ITERS: usize = 25;
+
+    let stones = simulate::run(stones.clone(), ITERS, blink);
+
+    Some(stones.values().sum())
+}
+
+pub fn part_two(stones: &HashMap<usize, usize>) -> Option<usize> {
+    const ITERS: usize = 75;
+
+    let stones = simulate::run(stones.clone(), ITERS, blink);
+
+    Some(stones.values().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = parse_input(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_one(&input.unwrap());
+        assert_eq!(result, Some(55312));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = parse_input(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_two(&input.unwrap());
+        assert_eq!(result, Some(65601038650482));
+    }
+}
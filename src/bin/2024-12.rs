@@ -0,0 +1,210 @@
+advent_of_code::solution!(2024, 12);
+
+use advent_of_code::grid::{Direction, Grid, Point};
+use advent_of_code::search::connected_components;
+
+const UP: Direction = Direction::Up;
+const DOWN: Direction = Direction::Down;
+const LEFT: Direction = Direction::Left;
+const RIGHT: Direction = Direction::Right;
+
+#[derive(Debug, Clone, Copy)]
+struct Plant {
+    position: Point,
+    plant: char,
+}
+
+impl PartialEq for Plant {
+    fn eq(&self, other: &Self) -> bool {
+        self.position == other.position && self.plant == other.plant
+    }
+}
+
+struct GardenPlot {
+    plants: Vec<Plant>,
+}
+
+impl GardenPlot {
+    fn new(plants: Vec<Plant>) -> Self {
+        GardenPlot { plants }
+    }
+
+    fn calc_price(&self) -> usize {
+        self.calc_area() * self.calc_perimeter()
+    }
+
+    fn calc_price_discounted(&self) -> usize {
+        self.calc_area() * self.calc_sides()
+    }
+
+    fn calc_area(&self) -> usize {
+        self.plants.len()
+    }
+
+    fn locate_plant_neighbour_in_that_dir(&self, plant: &Plant, dir: Direction) -> Option<&Plant> {
+        plant
+            .position
+            .checked_step(dir)
+            .and_then(|neighbour| self.plants.iter().find(|p| p.position == neighbour))
+    }
+
+    fn calc_perimeter(&self) -> usize {
+        self.plants.iter().fold(0, |perimeter, plant| {
+            perimeter
+                + Direction::CARDINAL
+                    .into_iter()
+                    .filter(|&dir| {
+                        self.locate_plant_neighbour_in_that_dir(plant, dir)
+                            .is_none()
+                    })
+                    .count()
+        })
+    }
+
+    fn calc_sides(&self) -> usize {
+        let mut sides = 0;
+
+        let bounding_box = (
+            (
+                self.plants.iter().map(|p| p.position.x).min().unwrap(),
+                self.plants.iter().map(|p| p.position.x).max().unwrap(),
+            ),
+            (
+                self.plants.iter().map(|p| p.position.y).min().unwrap(),
+                self.plants.iter().map(|p| p.position.y).max().unwrap(),
+            ),
+        );
+
+        // check up and down sides
+        for y in bounding_box.1 .0..=bounding_box.1 .1 {
+            let y_lvl_plants = self.plants.iter().filter(|p| p.position.y == y);
+            for plant in y_lvl_plants {
+                if self.locate_plant_neighbour_in_that_dir(plant, UP).is_none()
+                    && (self
+                        .locate_plant_neighbour_in_that_dir(plant, LEFT)
+                        .is_none_or(|left_neighbour| {
+                            self.locate_plant_neighbour_in_that_dir(left_neighbour, UP)
+                                .is_some()
+                        }))
+                {
+                    sides += 1;
+                }
+                if self
+                    .locate_plant_neighbour_in_that_dir(plant, DOWN)
+                    .is_none()
+                    && (self
+                        .locate_plant_neighbour_in_that_dir(plant, LEFT)
+                        .is_none_or(|left_neighbour| {
+                            self.locate_plant_neighbour_in_that_dir(left_neighbour, DOWN)
+                                .is_some()
+                        }))
+                {
+                    sides += 1;
+                }
+            }
+        }
+
+        // check left and right sides
+        for x in bounding_box.0 .0..=bounding_box.0 .1 {
+            let x_lvl_plants = self.plants.iter().filter(|p| p.position.x == x);
+            for plant in x_lvl_plants {
+                if self
+                    .locate_plant_neighbour_in_that_dir(plant, LEFT)
+                    .is_none()
+                    && (self
+                        .locate_plant_neighbour_in_that_dir(plant, UP)
+                        .is_none_or(|up_neighbour| {
+                            self.locate_plant_neighbour_in_that_dir(up_neighbour, LEFT)
+                                .is_some()
+                        }))
+                {
+                    sides += 1;
+                }
+                if self
+                    .locate_plant_neighbour_in_that_dir(plant, RIGHT)
+                    .is_none()
+                    && (self
+                        .locate_plant_neighbour_in_that_dir(plant, UP)
+                        .is_none_or(|up_neighbour| {
+                            self.locate_plant_neighbour_in_that_dir(up_neighbour, RIGHT)
+                                .is_some()
+                        }))
+                {
+                    sides += 1;
+                }
+            }
+        }
+
+        sides
+    }
+}
+
+struct Farm {
+    map: Grid<Plant>,
+}
+
+impl Farm {
+    fn new(map: Grid<char>) -> Self {
+        Farm {
+            map: Grid::from_fn(map.width(), map.height(), |position| Plant {
+                position,
+                plant: map[position],
+            }),
+        }
+    }
+
+    fn list_garden_plots(&self) -> Vec<GardenPlot> {
+        let map = &self.map;
+        connected_components(map.points(), |&position| {
+            map.neighbors4(position)
+                .filter(move |neighbour| map[*neighbour].plant == map[position].plant)
+        })
+        .into_iter()
+        .map(|positions| GardenPlot::new(positions.into_iter().map(|p| map[p]).collect()))
+        .collect()
+    }
+}
+
+fn parse_input(input: &str) -> Grid<char> {
+    input.parse().expect("map is not rectangular")
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let map = parse_input(input);
+    let farm = Farm::new(map);
+    let garden_plots = farm.list_garden_plots();
+
+    let full_price = garden_plots.iter().map(|plot| plot.calc_price()).sum();
+
+    Some(full_price)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let map = parse_input(input);
+    let farm = Farm::new(map);
+    let garden_plots = farm.list_garden_plots();
+
+    let full_price = garden_plots
+        .iter()
+        .map(|plot| plot.calc_price_discounted())
+        .sum();
+
+    Some(full_price)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(1930));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(1206));
+    }
+}
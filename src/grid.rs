@@ -0,0 +1,478 @@
+/// A rectangular 2D grid, as found in many puzzle inputs.
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// A position in a grid. `x` grows to the right, `y` grows downwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the point offset by `(dx, dy)`, `None` if a coordinate would become negative.
+    pub fn checked_offset(self, dx: isize, dy: isize) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+
+    /// Returns the neighboring point in a direction, `None` if a coordinate would become negative.
+    pub fn checked_step(self, direction: Direction) -> Option<Self> {
+        let (dx, dy) = direction.offset();
+        self.checked_offset(dx, dy)
+    }
+
+    /// Returns the offset from this point to another one.
+    pub fn delta(self, other: Self) -> (isize, isize) {
+        (
+            other.x as isize - self.x as isize,
+            other.y as isize - self.y as isize,
+        )
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A direction on a grid, `Up` points towards `y = 0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The four directions along the axes, clockwise starting with `Up`.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All eight directions, clockwise starting with `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// The change of `(x, y)` when stepping into this direction.
+    pub const fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    fn turn(self, eighths: usize) -> Self {
+        let index = Self::ALL.iter().position(|d| *d == self).unwrap();
+        Self::ALL[(index + eighths) % Self::ALL.len()]
+    }
+
+    /// Rotates by 90 degrees clockwise.
+    pub fn rotate_right(self) -> Self {
+        self.turn(2)
+    }
+
+    /// Rotates by 90 degrees counterclockwise.
+    pub fn rotate_left(self) -> Self {
+        self.turn(6)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Self::CARDINAL.contains(&self)
+    }
+
+    /// Reads an arrow, e.g. `^` or `>`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// Returns the arrow of a direction along the axes, `None` for diagonals.
+    pub fn to_arrow(self) -> Option<char> {
+        match self {
+            Direction::Up => Some('^'),
+            Direction::Right => Some('>'),
+            Direction::Down => Some('v'),
+            Direction::Left => Some('<'),
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A line of the input is not as long as the first one.
+    RaggedLine {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::RaggedLine {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has {found} cells, expected {expected} like the first line"
+            ),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid with the value of each cell computed from its point.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Point) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Point::new(x, y)))
+            .map(&mut f)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid with a cell per character of each line.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (i, line) in input.lines().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let found = cells.len() - len;
+
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::RaggedLine {
+                        line: i + 1,
+                        expected,
+                        found,
+                    });
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or_default(),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    fn index_of(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then_some(point.y * self.width + point.x)
+    }
+
+    fn point_of(&self, index: usize) -> Point {
+        Point::new(index % self.width, index / self.width)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Returns the neighboring point in a direction, `None` if it is outside the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        point
+            .checked_step(direction)
+            .filter(|next| self.contains(*next))
+    }
+
+    /// Returns the points next to a point along the axes that are inside the grid.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// Returns the points next to a point, including diagonals, that are inside the grid.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction))
+    }
+
+    /// Returns every point of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        (0..self.cells.len()).map(|i| self.point_of(i))
+    }
+
+    /// Returns every cell of the grid along with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point_of(i), cell))
+    }
+
+    /// Returns the points of the cells matching a predicate, row by row.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter_map(move |(point, cell)| predicate(cell).then_some(point))
+    }
+
+    /// Returns the point of the first cell matching a predicate, row by row.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.positions(predicate).next()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Returns the cells of a row, `None` if it is outside the grid.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Returns the cells of a column from top to bottom, `None` if it is outside the grid.
+    pub fn column(&self, x: usize) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| self.cells.iter().skip(x).step_by(self.width))
+    }
+
+    /// Returns the points and cells from `start` into a direction until the edge of the grid,
+    /// including `start`. Diagonal directions give a view of a diagonal.
+    pub fn ray(&self, start: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        std::iter::successors(Some(start).filter(|p| self.contains(*p)), move |point| {
+            self.step(*point, direction)
+        })
+        .map(|point| (point, &self[point]))
+    }
+
+    /// Converts every cell of the grid.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, |c| c)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        match self.get(point) {
+            Some(cell) => cell,
+            None => panic!(
+                "point {point} is outside the grid of {}x{}",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(cell) => cell,
+            None => panic!("point {point} is outside the grid of {width}x{height}"),
+        }
+    }
+}
+
+/// Draws the grid with a line per row, e.g. to print it while debugging.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Grid, GridError, Point};
+
+    const INPUT: &str = "ab.\n.c#\nd..\n";
+
+    #[test]
+    fn parses_grids() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid[Point::new(1, 1)], 'c');
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.find(|c| *c == '#'), Some(Point::new(2, 1)));
+        assert_eq!(grid.to_string(), INPUT);
+
+        let digits = Grid::parse("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.row(1), Some([3, 4].as_slice()));
+        assert_eq!(digits.column(1).unwrap().collect::<Vec<_>>(), [&2, &4]);
+        assert!(digits.column(2).is_none());
+        assert_eq!(Grid::from_fn(2, 2, |p| (p.y * 2 + p.x + 1) as u32), digits);
+    }
+
+    #[test]
+    fn errors_for_ragged_grids() {
+        assert_eq!(
+            "ab\nc\n".parse::<Grid<char>>(),
+            Err(GridError::RaggedLine {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(Grid::parse("", |c| c).unwrap().height(), 0);
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        let corner = Point::new(0, 0);
+        assert_eq!(
+            grid.neighbors4(corner).collect::<Vec<_>>(),
+            [Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(corner).count(), 3);
+        assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.step(Point::new(2, 2), Direction::Right), None);
+    }
+
+    #[test]
+    fn rotates_directions() {
+        assert_eq!(Direction::Up.rotate_right(), Direction::Right);
+        assert_eq!(Direction::Up.rotate_left(), Direction::Left);
+        assert_eq!(Direction::UpRight.rotate_right(), Direction::DownRight);
+        assert_eq!(Direction::Left.opposite(), Direction::Right);
+        assert_eq!(Direction::from_arrow('v'), Some(Direction::Down));
+        assert!(Direction::DownLeft.is_diagonal());
+    }
+
+    #[test]
+    fn views_rays() {
+        let grid: Grid<char> = INPUT.parse().unwrap();
+        let diagonal: String = grid
+            .ray(Point::new(0, 0), Direction::DownRight)
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(diagonal, "ac.");
+        assert_eq!(grid.ray(Point::new(3, 3), Direction::Up).count(), 0);
+        assert_eq!(Point::new(1, 0).checked_step(Direction::UpLeft), None);
+        assert_eq!(Point::new(1, 1).delta(Point::new(0, 3)), (-1, 2));
+    }
+}
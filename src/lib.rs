@@ -1,3 +1,8 @@
+pub mod grid;
+pub mod parse;
+pub mod search;
+pub mod simulate;
 pub mod template;
+pub mod visualize;
 
 // Use this file to add helper functions and additional modules.
@@ -1,79 +1,240 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, readme, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::commands::scaffold::ModuleTemplate;
+#[cfg(feature = "today")]
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
+/// Every solution in `src/bin`, see `build.rs`.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::bench::BenchSettings;
+    use advent_of_code::template::commands::scaffold::ModuleTemplate;
+    use advent_of_code::template::compare::DEFAULT_THRESHOLD;
+    use advent_of_code::template::output::OutputFormat;
+    use advent_of_code::template::{Day, PuzzleId, Year};
+    use advent_of_code::visualize::VisualizeOptions;
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
+            extract_examples: bool,
+            template: ModuleTemplate,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            force: bool,
+            format: OutputFormat,
+            visualize: Option<VisualizeOptions>,
+            watch: bool,
         },
         All {
-            release: bool,
+            year: Option<Year>,
+            jobs: usize,
+            format: OutputFormat,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            year: Option<Year>,
+            puzzle: Option<PuzzleId>,
             store: bool,
+            compare: Option<f64>,
+            jobs: usize,
+            memory: bool,
+            bench: BenchSettings,
+            format: OutputFormat,
+        },
+        History {
+            puzzle: PuzzleId,
+            svg: bool,
+            format: OutputFormat,
         },
+        Verify {
+            all: bool,
+            year: Option<Year>,
+            puzzle: Option<PuzzleId>,
+            format: OutputFormat,
+        },
+        Examples {
+            year: Option<Year>,
+            puzzle: Option<PuzzleId>,
+            format: OutputFormat,
+            watch: bool,
+        },
+        Readme,
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Resolves the year of a puzzle, falling back to the `AOC_YEAR` environment variable.
+    fn year_or_default(year: Option<Year>) -> Result<Year, Box<dyn std::error::Error>> {
+        year.or_else(Year::from_env)
+            .ok_or_else(|| "no year specified, pass `--year <year>` or set `AOC_YEAR`.".into())
+    }
+
+    fn puzzle(year: Year, day: Day) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        PuzzleId::new(year, day)
+            .ok_or_else(|| format!("day {day} is not part of the {year} event.").into())
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+        let format: OutputFormat = args.opt_value_from_str("--format")?.unwrap_or_default();
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+                year,
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                format,
             },
+            Some("time") if args.contains("--history") => {
+                let svg = args.contains("--svg");
+                let day = args
+                    .opt_free_from_str()?
+                    .ok_or("`--history` needs a day, e.g. `cargo time --history 6`.")?;
+
+                AppArguments::History {
+                    puzzle: puzzle(year_or_default(year)?, day)?,
+                    svg,
+                    format,
+                }
+            }
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                // benches stay serial unless asked for, as concurrent days skew the timings.
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let memory = args.contains("--memory");
+                let millis = |value: Option<u64>| value.map(Duration::from_millis);
+                let bench = BenchSettings {
+                    warmup: millis(args.opt_value_from_str("--warmup-ms")?),
+                    budget: millis(args.opt_value_from_str("--budget-ms")?),
+                    min_samples: args.opt_value_from_str("--min-samples")?,
+                    max_samples: args.opt_value_from_str("--max-samples")?,
+                };
+                let puzzle = match args.opt_free_from_str()? {
+                    Some(day) => Some(puzzle(year_or_default(year)?, day)?),
+                    None => None,
+                };
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    year,
+                    puzzle,
                     store,
+                    compare: (compare || threshold.is_some())
+                        .then(|| threshold.unwrap_or(DEFAULT_THRESHOLD)),
+                    jobs,
+                    memory,
+                    bench,
+                    format,
+                }
+            }
+            Some("readme") => AppArguments::Readme,
+            Some("verify") => {
+                let all = args.contains("--all");
+                let puzzle = match args.opt_free_from_str()? {
+                    Some(day) => Some(puzzle(year_or_default(year)?, day)?),
+                    None => None,
+                };
+
+                AppArguments::Verify {
+                    all,
+                    year,
+                    puzzle,
+                    format,
+                }
+            }
+            Some("examples") => {
+                let watch = args.contains("--watch");
+                let puzzle = match args.opt_free_from_str()? {
+                    Some(day) => Some(puzzle(year_or_default(year)?, day)?),
+                    None => None,
+                };
+                if watch && puzzle.is_none() {
+                    return Err("`--watch` requires a day, e.g. `cargo examples 1 --watch`.".into());
+                }
+
+                AppArguments::Examples {
+                    year,
+                    puzzle,
+                    format,
+                    watch,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year_or_default(year)?, args.free_from_str()?)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year_or_default(year)?, args.free_from_str()?)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: puzzle(year_or_default(year)?, args.free_from_str()?)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                extract_examples: args.contains("--extract-examples"),
+                template: ModuleTemplate {
+                    name: args
+                        .opt_value_from_str("--template")?
+                        .unwrap_or_else(|| "default".into()),
+                    answer_type: args
+                        .opt_value_from_str("--answer-type")?
+                        .unwrap_or_else(|| "u64".into()),
+                },
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let puzzle = puzzle(year_or_default(year)?, args.free_from_str()?)?;
+                let submit = args.opt_value_from_str("--submit")?;
+                let watch = args.contains("--watch");
+                if watch && submit.is_some() {
+                    return Err("`--watch` can not be combined with `--submit`.".into());
+                }
+                let visualize = args.contains("--visualize");
+                let defaults = VisualizeOptions::default();
+                let visualize_options = VisualizeOptions {
+                    fps: args.opt_value_from_str("--fps")?.unwrap_or(defaults.fps),
+                    output: args
+                        .opt_value_from_str("--export")?
+                        .unwrap_or(defaults.output),
+                };
+
+                AppArguments::Solve {
+                    puzzle,
+                    release: args.contains("--release"),
+                    submit,
+                    force: args.contains("--force"),
+                    dhat: args.contains("--dhat"),
+                    format,
+                    visualize: visualize.then_some(visualize_options),
+                    watch,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -102,38 +263,100 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All { year, jobs, format } => {
+                all::handle(solutions::SOLUTIONS, year, jobs, format)
+            }
+            AppArguments::Time {
+                year,
+                puzzle,
+                all,
+                store,
+                compare,
+                jobs,
+                memory,
+                bench,
+                format,
+            } => time::handle(
+                solutions::SOLUTIONS,
+                year,
+                puzzle,
+                all,
+                store,
+                compare,
+                jobs,
+                memory,
+                bench,
+                format,
+            ),
+            AppArguments::History {
+                puzzle,
+                svg,
+                format,
+            } => time::history(puzzle, svg, format),
+            AppArguments::Verify {
+                all,
+                year,
+                puzzle,
+                format,
+            } => verify::handle(solutions::SOLUTIONS, year, puzzle, all, format),
+            AppArguments::Examples {
+                year,
+                puzzle,
+                format,
+                watch,
+            } => match puzzle.filter(|_| watch) {
+                Some(puzzle) => examples::watch(puzzle, format),
+                None => examples::handle(solutions::SOLUTIONS, year, puzzle, format),
+            },
+            AppArguments::Readme => readme::handle(solutions::SOLUTIONS),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
             AppArguments::Scaffold {
-                day,
+                puzzle,
                 download,
                 overwrite,
+                extract_examples,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // extracting the examples of a scaffolded day only updates its examples.
+                if overwrite || !(extract_examples && scaffold::is_scaffolded(puzzle)) {
+                    scaffold::handle(puzzle, &template, overwrite);
+                }
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
+                }
+                if extract_examples {
+                    scaffold::extract_examples(puzzle, &template);
+                } else if download {
+                    scaffold::update_title(puzzle, &template);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                force,
+                format,
+                visualize,
+                watch,
+            } => solve::handle(
+                puzzle, release, dhat, submit, force, format, visualize, watch,
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        let template = ModuleTemplate::default();
+                        scaffold::handle(puzzle, &template, false);
+                        download::handle(puzzle);
+                        scaffold::update_title(puzzle, &template);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run while an advent of code event \
+                            is running. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,419 @@
+/// Helpers to parse puzzle inputs. Lines are read without their line endings and trailing
+/// whitespace, so inputs with `\r\n` or a trailing blank line parse the same as clean ones.
+/// Errors name the line and column they occurred at.
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    num::ParseIntError,
+    ops::RangeInclusive,
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use regex::Regex;
+
+use crate::grid::{Grid, GridError};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    InvalidInteger { text: String, source: ParseIntError },
+    MissingSeparator { separator: String },
+    MissingValues,
+    InvalidDigit { found: char },
+    WrongBlockCount { expected: usize, found: usize },
+    RaggedLine { expected: usize, found: usize },
+}
+
+/// An error while parsing the input, at a 1-based line and column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ErrorKind,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ErrorKind::InvalidInteger { text, .. } => {
+                write!(f, "\"{text}\" is not a valid integer")
+            }
+            ErrorKind::MissingSeparator { separator } => write!(f, "expected \"{separator}\""),
+            ErrorKind::MissingValues => write!(f, "expected at least one value"),
+            ErrorKind::InvalidDigit { found } => write!(f, "expected a digit, found '{found}'"),
+            ErrorKind::WrongBlockCount { expected, found } => write!(
+                f,
+                "expected {expected} blocks separated by blank lines, found {found}"
+            ),
+            ErrorKind::RaggedLine { expected, found } => write!(
+                f,
+                "line has {found} cells, expected {expected} like the first line"
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ErrorKind::InvalidInteger { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A part of the input along with the 1-based line and column it starts at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    pub line: usize,
+    pub column: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Span<'a> {
+    fn error(&self, kind: ErrorKind) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+
+    /// Returns the span of `text[start..end]`.
+    fn slice(&self, start: usize, end: usize) -> Span<'a> {
+        Span {
+            line: self.line,
+            column: self.column + self.text[..start].chars().count(),
+            text: &self.text[start..end],
+        }
+    }
+
+    /// Returns the span without surrounding whitespace.
+    pub fn trim(&self) -> Span<'a> {
+        let start = self.text.len() - self.text.trim_start().len();
+        let end = self.text.trim_end().len().max(start);
+        self.slice(start, end)
+    }
+
+    /// Parses the span as an integer, ignoring surrounding whitespace.
+    pub fn parse<T: FromStr<Err = ParseIntError>>(&self) -> Result<T, ParseError> {
+        let span = self.trim();
+        span.text.parse().map_err(|source| {
+            span.error(ErrorKind::InvalidInteger {
+                text: span.text.to_string(),
+                source,
+            })
+        })
+    }
+
+    /// Splits the span at the first occurrence of `separator`.
+    pub fn split_once(&self, separator: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        match self.text.find(separator) {
+            Some(index) => Ok((
+                self.slice(0, index),
+                self.slice(index + separator.len(), self.text.len()),
+            )),
+            None => Err(self.slice(self.text.len(), self.text.len()).error(
+                ErrorKind::MissingSeparator {
+                    separator: separator.to_string(),
+                },
+            )),
+        }
+    }
+
+    /// Splits the span at every occurrence of `separator`.
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Span<'a>> + 'a {
+        let span = *self;
+        let mut start = 0;
+        span.text
+            .match_indices(separator)
+            .map(|(index, _)| index)
+            .chain([span.text.len()])
+            .map(move |end| {
+                let part = span.slice(start, end);
+                start = end + separator.len();
+                part
+            })
+    }
+
+    /// Splits the span at runs of whitespace, skipping empty parts.
+    pub fn words(&self) -> impl Iterator<Item = Span<'a>> + 'a {
+        static WORD: OnceLock<Regex> = OnceLock::new();
+        let word = WORD.get_or_init(|| Regex::new(r"\S+").unwrap());
+
+        let span = *self;
+        word.find_iter(span.text)
+            .map(move |m| span.slice(m.start(), m.end()))
+    }
+
+    /// Returns every integer of the span, ignoring anything in between, e.g. `[-3, 4]` for
+    /// `x=-3, y=4`. Negative integers are an error if `T` is unsigned, see [`Span::ranges`] for
+    /// ranges like `1-3`.
+    pub fn integers<T: FromStr<Err = ParseIntError>>(&self) -> Result<Vec<T>, ParseError> {
+        static INTEGER: OnceLock<Regex> = OnceLock::new();
+        let integer = INTEGER.get_or_init(|| Regex::new(r"-?\d+").unwrap());
+
+        integer
+            .find_iter(self.text)
+            .map(|m| self.slice(m.start(), m.end()).parse())
+            .collect()
+    }
+
+    /// Returns every range of the span, ignoring anything in between, e.g. `[1..=3, 5..=10]` for
+    /// `1-3 a: 5-10`. The bounds are read without a sign.
+    pub fn ranges<T: FromStr<Err = ParseIntError>>(
+        &self,
+    ) -> Result<Vec<RangeInclusive<T>>, ParseError> {
+        static RANGE: OnceLock<Regex> = OnceLock::new();
+        let range = RANGE.get_or_init(|| Regex::new(r"(\d+)-(\d+)").unwrap());
+
+        range
+            .captures_iter(self.text)
+            .map(|c| {
+                let (start, end) = (c.get(1).unwrap(), c.get(2).unwrap());
+                Ok(self.slice(start.start(), start.end()).parse()?
+                    ..=self.slice(end.start(), end.end()).parse()?)
+            })
+            .collect()
+    }
+
+    /// Parses a pair of integers separated by `separator`, e.g. `47|53`.
+    pub fn pair<T: FromStr<Err = ParseIntError>>(
+        &self,
+        separator: &str,
+    ) -> Result<(T, T), ParseError> {
+        let (a, b) = self.split_once(separator)?;
+        Ok((a.parse()?, b.parse()?))
+    }
+
+    /// Parses a record of a key and its integer values, e.g. `190: 10 19`. The values are
+    /// separated by whitespace and there must be at least one.
+    pub fn record<T: FromStr<Err = ParseIntError>>(
+        &self,
+        separator: &str,
+    ) -> Result<(T, Vec<T>), ParseError> {
+        let (key, values) = self.split_once(separator)?;
+        let key = key.parse()?;
+        let values = values
+            .words()
+            .map(|value| value.parse())
+            .collect::<Result<Vec<T>, _>>()?;
+
+        if values.is_empty() {
+            return Err(self
+                .slice(self.text.len(), self.text.len())
+                .error(ErrorKind::MissingValues));
+        }
+
+        Ok((key, values))
+    }
+}
+
+impl Display for Span<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns the lines of the input without line endings and trailing whitespace.
+/// Blank lines at the end of the input are skipped.
+pub fn lines(input: &str) -> impl Iterator<Item = Span<'_>> {
+    input.trim_end().lines().enumerate().map(|(i, line)| Span {
+        line: i + 1,
+        column: 1,
+        text: line.trim_end(),
+    })
+}
+
+/// Splits the input into blocks of lines that are separated by blank lines.
+pub fn blocks(input: &str) -> Vec<Vec<Span<'_>>> {
+    let mut blocks: Vec<Vec<Span>> = vec![];
+    let mut is_new_block = true;
+
+    for line in lines(input) {
+        if line.text.is_empty() {
+            is_new_block = true;
+        } else if is_new_block {
+            blocks.push(vec![line]);
+            is_new_block = false;
+        } else {
+            blocks.last_mut().unwrap().push(line);
+        }
+    }
+
+    blocks
+}
+
+/// Splits the input into exactly `N` blocks of lines that are separated by blank lines.
+pub fn exact_blocks<const N: usize>(input: &str) -> Result<[Vec<Span<'_>>; N], ParseError> {
+    let blocks = blocks(input);
+    let found = blocks.len();
+
+    blocks.try_into().map_err(|_| ParseError {
+        line: lines(input).count().max(1),
+        column: 1,
+        kind: ErrorKind::WrongBlockCount { expected: N, found },
+    })
+}
+
+/// Returns every integer of the input, see [`Span::integers`].
+pub fn integers<T: FromStr<Err = ParseIntError>>(input: &str) -> Result<Vec<T>, ParseError> {
+    lines(input).try_fold(vec![], |mut values, line| {
+        values.extend(line.integers()?);
+        Ok(values)
+    })
+}
+
+/// Reads a grid of single digits, e.g. a height map.
+pub fn digit_grid(input: &str) -> Result<Grid<u32>, ParseError> {
+    let lines: Vec<Span> = lines(input).collect();
+
+    for line in &lines {
+        if let Some((index, found)) = line.text.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+            return Err(line
+                .slice(index, index)
+                .error(ErrorKind::InvalidDigit { found }));
+        }
+    }
+
+    let text = lines
+        .iter()
+        .map(|line| line.text)
+        .collect::<Vec<_>>()
+        .join("\n");
+    Grid::parse(&text, |c| c.to_digit(10).unwrap()).map_err(|e| match e {
+        GridError::RaggedLine {
+            line,
+            expected,
+            found,
+        } => ParseError {
+            line,
+            column: found.min(expected) + 1,
+            kind: ErrorKind::RaggedLine { expected, found },
+        },
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{blocks, digit_grid, exact_blocks, integers, lines, ErrorKind, ParseError};
+    use crate::grid::Point;
+
+    #[test]
+    fn reads_lines_and_blocks() {
+        let input = "47|53\r\n97|13  \r\n\r\n75,47\r\n\r\n";
+        assert_eq!(
+            lines(input).map(|l| l.text).collect::<Vec<_>>(),
+            ["47|53", "97|13", "", "75,47"]
+        );
+
+        let blocks = blocks(input);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[1][0].line, 4);
+
+        let [rules, updates] = exact_blocks::<2>(input).unwrap();
+        assert_eq!(rules[1].pair::<u32>("|"), Ok((97, 13)));
+        assert_eq!(updates[0].integers::<u32>(), Ok(vec![75, 47]));
+
+        assert_eq!(
+            exact_blocks::<3>(input).unwrap_err().kind,
+            ErrorKind::WrongBlockCount {
+                expected: 3,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(
+            integers::<i64>("p=0,4 v=-3,3\np=6,3 v=-1,-3\n"),
+            Ok(vec![0, 4, -3, 3, 6, 3, -1, -3])
+        );
+        assert_eq!(integers::<u32>("125 17\n"), Ok(vec![125, 17]));
+
+        assert_eq!(integers::<i32>("1-3 a: 5--10"), Ok(vec![1, -3, 5, -10]));
+
+        let line = lines("1-3 a: 5-10").next().unwrap();
+        assert_eq!(line.ranges::<usize>(), Ok(vec![1..=3, 5..=10]));
+
+        let error = integers::<u32>("x=-3").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 3: \"-3\" is not a valid integer"
+        );
+
+        let error = integers::<u8>("1 2\n3 300").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: \"300\" is not a valid integer"
+        );
+    }
+
+    #[test]
+    fn parses_records() {
+        let line = lines("190: 10 19").next().unwrap();
+        assert_eq!(line.record::<u64>(":"), Ok((190, vec![10, 19])));
+
+        let fields: Vec<&str> = line.split(" ").map(|s| s.text).collect();
+        assert_eq!(fields, ["190:", "10", "19"]);
+        assert_eq!(line.split(" ").nth(2).unwrap().column, 9);
+
+        let line = lines("a\n12 x3").nth(1).unwrap();
+        let error = line.pair::<u32>(" ").unwrap_err();
+        assert_eq!(
+            error,
+            ParseError {
+                line: 2,
+                column: 4,
+                kind: ErrorKind::InvalidInteger {
+                    text: "x3".into(),
+                    source: "x3".parse::<u32>().unwrap_err()
+                }
+            }
+        );
+        assert_eq!(
+            line.split_once("|").unwrap_err().to_string(),
+            "line 2, column 6: expected \"|\""
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        let line = lines("190: 10 x 19").next().unwrap();
+        let error = line.record::<u64>(":").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 9));
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 9: \"x\" is not a valid integer"
+        );
+
+        let line = lines("5:").next().unwrap();
+        assert_eq!(
+            line.record::<u64>(":").unwrap_err().to_string(),
+            "line 1, column 3: expected at least one value"
+        );
+    }
+
+    #[test]
+    fn reads_digit_grids() {
+        let grid = digit_grid("012\r\n345\r\n").unwrap();
+        assert_eq!(grid[Point::new(2, 1)], 5);
+
+        let error = digit_grid("012\n3.5\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.kind, ErrorKind::InvalidDigit { found: '.' });
+
+        let error = digit_grid("012\n34\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+}
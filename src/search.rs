@@ -0,0 +1,357 @@
+/// Generic searches over graphs, defined by a closure returning the neighbors of a node.
+/// This applies to grids (e.g. with `Grid::neighbors4`) as well as to arbitrary graphs.
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Follows the parents of `node` back to the start of a search, returns the path from the start.
+fn build_path<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, node: N) -> Vec<N> {
+    let mut path = vec![node];
+    while let Some(parent) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+    path.reverse();
+    path
+}
+
+/// Breadth-first search, returns a shortest path (by number of steps) from `start` to a goal,
+/// including both.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut visited = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(build_path(&parents, node));
+        }
+        for next in neighbors(&node) {
+            if visited.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the number of steps from `start` to every node reachable from it.
+pub fn bfs_distances<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in neighbors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Depth-first search, returns a path from `start` to a goal, including both. The path is not
+/// necessarily the shortest one.
+pub fn dfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![(start, None)];
+
+    while let Some((node, parent)) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        if let Some(parent) = parent {
+            parents.insert(node.clone(), parent);
+        }
+        if is_goal(&node) {
+            return Some(build_path(&parents, node));
+        }
+        for next in neighbors(&node) {
+            if !visited.contains(&next) {
+                stack.push((next, Some(node.clone())));
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns every node reachable from `start`, including it, in the order they were found.
+pub fn flood_fill<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::from([start.clone()]);
+    let mut nodes = vec![start];
+    let mut index = 0;
+
+    while let Some(node) = nodes.get(index).cloned() {
+        for next in neighbors(&node) {
+            if visited.insert(next.clone()) {
+                nodes.push(next);
+            }
+        }
+        index += 1;
+    }
+
+    nodes
+}
+
+/// Groups nodes into the components that are connected by `neighbors`, e.g. the regions of a grid.
+/// Components are returned in the order of their first node in `nodes`.
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen: HashSet<N> = HashSet::new();
+    let mut components = vec![];
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+        let component = flood_fill(node, &mut neighbors);
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+/// Counts the distinct paths from `start` to a goal, memoizing the count of every node. The graph
+/// must not have cycles, e.g. because every step goes uphill. Paths end at the first goal.
+pub fn count_paths<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N, I>(
+        node: &N,
+        neighbors: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        cache: &mut HashMap<N, usize>,
+    ) -> usize
+    where
+        N: Clone + Eq + Hash,
+        I: IntoIterator<Item = N>,
+    {
+        if is_goal(node) {
+            return 1;
+        }
+        if let Some(paths) = cache.get(node) {
+            return *paths;
+        }
+        let paths = neighbors(node)
+            .into_iter()
+            .map(|next| count(&next, neighbors, is_goal, cache))
+            .sum();
+        cache.insert(node.clone(), paths);
+        paths
+    }
+
+    count(&start, &mut neighbors, &mut is_goal, &mut HashMap::new())
+}
+
+/// Dijkstra's algorithm, returns a cheapest path from `start` to a goal along with its cost.
+/// `neighbors` returns the next nodes along with the cost of moving to them.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+/// A* search, returns a cheapest path from `start` to a goal along with its cost. `heuristic`
+/// estimates the remaining cost to a goal and must never overestimate it.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut costs: HashMap<N, C> = HashMap::from([(start.clone(), C::default())]);
+    // nodes are queued by index, so they don't need to be ordered themselves.
+    let mut nodes = vec![start.clone()];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let node = nodes[index].clone();
+        if costs.get(&node).is_some_and(|best| *best < cost) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((build_path(&parents, node), cost));
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_some_and(|best| *best <= next_cost) {
+                continue;
+            }
+            costs.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), node.clone());
+            queue.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        astar, bfs, bfs_distances, connected_components, count_paths, dfs, dijkstra, flood_fill,
+    };
+    use crate::grid::{Grid, Point};
+
+    const MAZE: &str = "\
+S.#.
+.##.
+...E
+";
+
+    fn open_neighbors(grid: &Grid<char>) -> impl FnMut(&Point) -> Vec<Point> + '_ {
+        |point| {
+            grid.neighbors4(*point)
+                .filter(|next| grid[*next] != '#')
+                .collect()
+        }
+    }
+
+    #[test]
+    fn searches_grids() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let start = grid.find(|c| *c == 'S').unwrap();
+        let end = grid.find(|c| *c == 'E').unwrap();
+
+        let path = bfs(start, open_neighbors(&grid), |p| *p == end).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!((path[0], path[5]), (start, end));
+
+        let path = dfs(start, open_neighbors(&grid), |p| *p == end).unwrap();
+        assert_eq!((path[0], *path.last().unwrap()), (start, end));
+
+        assert_eq!(bfs_distances(start, open_neighbors(&grid))[&end], 5);
+        assert_eq!(flood_fill(start, open_neighbors(&grid)).len(), 9);
+        assert_eq!(bfs(start, open_neighbors(&grid), |p| p.x > 3), None);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        let graph = HashMap::from([
+            ('a', vec![('b', 7), ('c', 2)]),
+            ('b', vec![('d', 1)]),
+            ('c', vec![('b', 3), ('d', 8)]),
+            ('d', vec![]),
+        ]);
+        let neighbors = |node: &char| graph[node].clone();
+
+        assert_eq!(
+            dijkstra('a', neighbors, |node| *node == 'd'),
+            Some((vec!['a', 'c', 'b', 'd'], 6))
+        );
+        assert_eq!(
+            astar('a', neighbors, |_| 0, |node| *node == 'd'),
+            Some((vec!['a', 'c', 'b', 'd'], 6))
+        );
+        assert_eq!(dijkstra('d', neighbors, |node| *node == 'a'), None);
+    }
+
+    #[test]
+    fn finds_paths_with_heuristics() {
+        let grid: Grid<char> = MAZE.parse().unwrap();
+        let end = Point::new(3, 2);
+        let (path, cost) = astar(
+            Point::new(0, 0),
+            |p| open_neighbors(&grid)(p).into_iter().map(|next| (next, 1)),
+            |p| p.manhattan_distance(end),
+            |p| *p == end,
+        )
+        .unwrap();
+        assert_eq!((path.len(), cost), (6, 5));
+    }
+
+    #[test]
+    fn groups_components() {
+        let grid: Grid<char> = "aab\nbab\n".parse().unwrap();
+        let components = connected_components(grid.points(), |p| {
+            grid.neighbors4(*p)
+                .filter(|next| grid[*next] == grid[*p])
+                .collect::<Vec<_>>()
+        });
+        let sizes: Vec<usize> = components.iter().map(Vec::len).collect();
+        assert_eq!(sizes, [3, 2, 1]);
+    }
+
+    #[test]
+    fn counts_paths() {
+        // every step goes one right or one down on a 3x3 grid.
+        let paths = count_paths(
+            (0, 0),
+            |&(x, y)| {
+                [(x + 1, y), (x, y + 1)]
+                    .into_iter()
+                    .filter(|(x, y)| *x < 3 && *y < 3)
+            },
+            |node| *node == (2, 2),
+        );
+        assert_eq!(paths, 6);
+    }
+}
@@ -0,0 +1,186 @@
+/// Helpers for puzzles that simulate a state step by step, e.g. to detect when a state repeats.
+use std::{collections::HashMap, hash::Hash};
+
+/// A cycle of states: the state after `start + length` steps equals the one after `start` steps,
+/// so the states from `start` on repeat forever.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Returns the smallest number of steps that reaches the same state as `steps` steps.
+    pub fn equivalent_step(&self, steps: usize) -> usize {
+        if steps < self.start {
+            steps
+        } else {
+            self.start + (steps - self.start) % self.length
+        }
+    }
+}
+
+/// Runs a simulation until a state repeats, remembering every state seen. Returns `None` if the
+/// simulation ends because `step` returns `None`, e.g. when a guard leaves the map.
+pub fn find_cycle<S>(initial: S, mut step: impl FnMut(&S) -> Option<S>) -> Option<Cycle>
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut state = initial;
+    let mut steps = 0;
+
+    loop {
+        if let Some(start) = seen.insert(state.clone(), steps) {
+            return Some(Cycle {
+                start,
+                length: steps - start,
+            });
+        }
+        state = step(&state)?;
+        steps += 1;
+    }
+}
+
+/// Finds the cycle of an endless simulation with Floyd's algorithm. Unlike [`find_cycle`], this
+/// only keeps a few states in memory, but calls `step` about three times as often.
+pub fn floyd<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut slow = step(&initial);
+    let mut fast = step(&slow);
+    while slow != fast {
+        slow = step(&slow);
+        fast = step(&fast);
+        fast = step(&fast);
+    }
+
+    let mut start = 0;
+    slow = initial;
+    while slow != fast {
+        slow = step(&slow);
+        fast = step(&fast);
+        start += 1;
+    }
+
+    let mut length = 1;
+    fast = step(&slow);
+    while slow != fast {
+        fast = step(&fast);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle of an endless simulation with Brent's algorithm. Like [`floyd`], this only
+/// keeps a few states in memory, but usually needs fewer steps.
+pub fn brent<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Returns the state after `steps` steps.
+pub fn run<S>(initial: S, steps: usize, mut step: impl FnMut(&S) -> S) -> S {
+    (0..steps).fold(initial, |state, _| step(&state))
+}
+
+/// Returns the state after `steps` steps of a deterministic simulation. Once a state repeats, the
+/// remaining steps are skipped by extrapolating the cycle, so e.g. a billion steps are feasible.
+pub fn fast_forward<S>(initial: S, steps: usize, mut step: impl FnMut(&S) -> S) -> S
+where
+    S: Clone + Eq + Hash,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut history = vec![];
+    let mut state = initial;
+
+    for current in 0..steps {
+        if let Some(start) = seen.insert(state.clone(), current) {
+            let cycle = Cycle {
+                start,
+                length: current - start,
+            };
+            return history.swap_remove(cycle.equivalent_step(steps));
+        }
+        let next = step(&state);
+        history.push(state);
+        state = next;
+    }
+
+    state
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, fast_forward, find_cycle, floyd, run, Cycle};
+
+    /// Steps through 0, 1, 2, 3, 4, 5, 2, 3, 4, 5, ...
+    fn step(n: &u32) -> u32 {
+        if *n == 5 {
+            2
+        } else {
+            n + 1
+        }
+    }
+
+    const CYCLE: Cycle = Cycle {
+        start: 2,
+        length: 4,
+    };
+
+    #[test]
+    fn finds_cycles() {
+        assert_eq!(find_cycle(0, |n| Some(step(n))), Some(CYCLE));
+        assert_eq!(floyd(0, step), CYCLE);
+        assert_eq!(brent(0, step), CYCLE);
+        assert_eq!(
+            brent(7, |_| 7),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn ends_without_cycles() {
+        assert_eq!(find_cycle(0, |n| (*n < 10).then_some(n + 1)), None);
+    }
+
+    #[test]
+    fn fast_forwards() {
+        assert_eq!(CYCLE.equivalent_step(1), 1);
+        assert_eq!(CYCLE.equivalent_step(7), 3);
+
+        for steps in 0..20 {
+            assert_eq!(fast_forward(0, steps, step), run(0, steps, step));
+        }
+        assert_eq!(fast_forward(0, 1_000_000_000_000, step), 4);
+    }
+}
@@ -0,0 +1,336 @@
+use std::{error::Error, fmt::Display};
+
+/// The answer of a solution part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// Letters drawn with lit (e.g. `#`) and dark (`.` or ` `) pixels across multiple lines.
+    Art(String),
+}
+
+impl Answer {
+    /// The value of the answer as it is submitted, stored and compared.
+    /// Art is read as letters if possible and kept as is otherwise.
+    pub fn value(&self) -> String {
+        match self {
+            Answer::Integer(n) => n.to_string(),
+            Answer::Text(s) => s.clone(),
+            Answer::Art(art) => ocr(art).unwrap_or_else(|| art.clone()),
+        }
+    }
+
+    /// The value to submit, `None` if the answer is art that can't be read as letters.
+    pub fn submission(&self) -> Option<String> {
+        match self {
+            Answer::Art(art) => ocr(art),
+            _ => Some(self.value()),
+        }
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Art(_))
+    }
+
+    /// The kind of the answer, e.g. for machine-readable output.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Integer(_) => "integer",
+            Answer::Text(_) => "text",
+            Answer::Art(_) => "art",
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Text(s) | Answer::Art(s) => f.write_str(s),
+        }
+    }
+}
+
+/// Converts the return value of a solution part to an [`Answer`].
+pub trait ToAnswer {
+    fn to_answer(&self) -> Answer;
+}
+
+impl ToAnswer for Answer {
+    fn to_answer(&self) -> Answer {
+        self.clone()
+    }
+}
+
+macro_rules! impl_to_answer_for_integers {
+    ($($t:ty),*) => {
+        $(
+            impl ToAnswer for $t {
+                fn to_answer(&self) -> Answer {
+                    Answer::Integer(i128::from(*self))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_answer_for_integers!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl ToAnswer for usize {
+    fn to_answer(&self) -> Answer {
+        // NOTE: `usize` is at most 64 bits wide on supported platforms.
+        Answer::Integer(*self as i128)
+    }
+}
+
+impl ToAnswer for isize {
+    fn to_answer(&self) -> Answer {
+        Answer::Integer(*self as i128)
+    }
+}
+
+impl ToAnswer for u128 {
+    fn to_answer(&self) -> Answer {
+        i128::try_from(*self).map_or_else(|_| Answer::Text(self.to_string()), Answer::Integer)
+    }
+}
+
+impl ToAnswer for str {
+    fn to_answer(&self) -> Answer {
+        if self.trim_end().contains('\n') {
+            Answer::Art(self.to_string())
+        } else {
+            Answer::Text(self.to_string())
+        }
+    }
+}
+
+impl ToAnswer for String {
+    fn to_answer(&self) -> Answer {
+        self.as_str().to_answer()
+    }
+}
+
+impl ToAnswer for &str {
+    fn to_answer(&self) -> Answer {
+        (*self).to_answer()
+    }
+}
+
+impl ToAnswer for char {
+    fn to_answer(&self) -> Answer {
+        Answer::Text(self.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The messages of an error and its sources, outermost error first.
+pub type ErrorChain = Vec<String>;
+
+/// The return value of a solution part, either `Option<T>` or `Result<T, E>`.
+pub trait PartOutput {
+    /// The answer of the part, `Ok(None)` if the part is not solved yet.
+    fn to_outcome(&self) -> Result<Option<Answer>, ErrorChain>;
+}
+
+impl<T: ToAnswer> PartOutput for Option<T> {
+    fn to_outcome(&self) -> Result<Option<Answer>, ErrorChain> {
+        Ok(self.as_ref().map(ToAnswer::to_answer))
+    }
+}
+
+impl<T: ToAnswer, E: Error> PartOutput for Result<T, E> {
+    fn to_outcome(&self) -> Result<Option<Answer>, ErrorChain> {
+        match self {
+            Ok(value) => Ok(Some(value.to_answer())),
+            Err(err) => Err(error_chain(err)),
+        }
+    }
+}
+
+fn error_chain(err: &dyn Error) -> ErrorChain {
+    let mut chain = vec![err.to_string()];
+    let mut source = err.source();
+
+    while let Some(err) = source {
+        chain.push(err.to_string());
+        source = err.source();
+    }
+
+    chain
+}
+
+/* -------------------------------------------------------------------------- */
+
+const LETTER_HEIGHT: usize = 6;
+
+/// Letters of the font used by puzzles with art answers, with blank columns trimmed.
+const LETTERS: [(char, &str); 18] = [
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('I', "###.#..#..#..#.###"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Y', "#...##...#.#.#...#....#....#.."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+fn is_lit(c: char) -> bool {
+    !c.is_whitespace() && c != '.'
+}
+
+/// Reads the letters drawn by an art answer. Returns `None` if any letter is not recognized.
+pub fn ocr(art: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect::<Vec<bool>>())
+        .skip_while(|row| !row.contains(&true))
+        .collect();
+
+    let rows: Vec<Vec<bool>> = match rows.iter().rposition(|row| row.contains(&true)) {
+        Some(last) => rows[..=last].to_vec(),
+        None => return None,
+    };
+
+    if rows.len() != LETTER_HEIGHT {
+        return None;
+    }
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let is_blank_column = |x: usize| rows.iter().all(|row| !row.get(x).copied().unwrap_or(false));
+
+    let mut letters = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_blank_column(x) {
+            x += 1;
+        }
+
+        let glyph: String = rows
+            .iter()
+            .flat_map(|row| {
+                (start..x).map(|i| {
+                    if row.get(i).copied().unwrap_or(false) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+            })
+            .collect();
+
+        let (letter, _) = LETTERS.iter().find(|(_, pixels)| *pixels == glyph)?;
+        letters.push(*letter);
+    }
+
+    Some(letters)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{error::Error, fmt::Display, num::ParseIntError};
+
+    use super::{ocr, Answer, PartOutput, ToAnswer};
+
+    const ART: &str = "\
+#..#.####.###..#....
+#..#.#....#..#.#....
+####.###..#..#.#....
+#..#.#....###..#....
+#..#.#....#.#..#....
+#..#.####.#..#.####.
+";
+
+    #[test]
+    fn converts_values() {
+        assert_eq!(42_u32.to_answer(), Answer::Integer(42));
+        assert_eq!((-1_i64).to_answer(), Answer::Integer(-1));
+        assert_eq!(7_usize.to_answer(), Answer::Integer(7));
+        assert_eq!(u128::MAX.to_answer(), Answer::Text(u128::MAX.to_string()));
+        assert_eq!("abc".to_answer(), Answer::Text("abc".into()));
+        assert_eq!(
+            "1,2,3\n".to_string().to_answer(),
+            Answer::Text("1,2,3\n".into())
+        );
+        assert_eq!(ART.to_answer(), Answer::Art(ART.into()));
+    }
+
+    #[test]
+    fn reads_art() {
+        assert_eq!(ocr(ART), Some("HERL".into()));
+        assert_eq!(
+            ocr(&ART.replace('#', "█").replace('.', " ")),
+            Some("HERL".into())
+        );
+        assert_eq!(ocr(&format!("\n{ART}\n")), Some("HERL".into()));
+        assert_eq!(ocr("#..#\n#..#"), None);
+    }
+
+    #[test]
+    fn values_of_answers() {
+        let art = ART.to_answer();
+        assert_eq!(art.value(), "HERL");
+        assert_eq!(art.submission(), Some("HERL".into()));
+
+        let unreadable = Answer::Art("#\n#\n".into());
+        assert_eq!(unreadable.value(), "#\n#\n");
+        assert_eq!(unreadable.submission(), None);
+
+        assert_eq!(Answer::Integer(12).submission(), Some("12".into()));
+    }
+
+    #[derive(Debug)]
+    struct ParseError(ParseIntError);
+
+    impl Display for ParseError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("could not parse input")
+        }
+    }
+
+    impl Error for ParseError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(1_u8).to_outcome(), Ok(Some(Answer::Integer(1))));
+        assert_eq!(None::<u8>.to_outcome(), Ok(None));
+        assert_eq!(
+            Ok::<_, ParseIntError>("a").to_outcome(),
+            Ok(Some(Answer::Text("a".into())))
+        );
+
+        let err = "x".parse::<u32>().map_err(ParseError);
+        assert_eq!(
+            err.to_outcome(),
+            Err(vec![
+                "could not parse input".to_string(),
+                "invalid digit found in string".to_string()
+            ])
+        );
+    }
+}
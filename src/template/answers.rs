@@ -0,0 +1,308 @@
+/// Known answers of solved puzzles, used to verify that solutions still produce them.
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, Year};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Larger integers can't be represented exactly by a JSON number.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+/// Represents the known answers of a single puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KnownAnswers {
+    pub puzzle: PuzzleId,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl KnownAnswers {
+    fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the known answers of a set of puzzles.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<KnownAnswers>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Returns the known answer of a part, if any.
+    pub fn get(&self, puzzle: PuzzleId, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|a| a.puzzle == puzzle)
+            .and_then(|a| a.part(part))
+    }
+
+    /// Set the answer of a part, overwriting a previously known answer.
+    pub fn set(&mut self, puzzle: PuzzleId, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|a| a.puzzle == puzzle) {
+            Some(index) => index,
+            None => {
+                self.data.push(KnownAnswers {
+                    puzzle,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.puzzle);
+                self.data.iter().position(|a| a.puzzle == puzzle).unwrap()
+            }
+        };
+
+        let entry = &mut self.data[index];
+        match part {
+            1 => entry.part_1 = Some(answer.into()),
+            2 => entry.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Store the answer of a part in the answers file.
+pub fn record(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = Answers::read_from_file();
+    answers.set(puzzle, part, answer);
+    answers.store_file()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of comparing the answer of a part with its known answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The part produced the known answer.
+    Pass,
+    /// The part produced a different answer, or none at all.
+    Fail,
+    /// There is no known answer to compare against.
+    Missing,
+}
+
+impl Verdict {
+    pub fn new(expected: Option<&str>, actual: Option<&str>) -> Self {
+        match (expected, actual) {
+            (None, _) => Verdict::Missing,
+            (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+            _ => Verdict::Fail,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail => "fail",
+            Verdict::Missing => "missing",
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(KnownAnswers::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&KnownAnswers> for JsonValue {
+    fn from(value: &KnownAnswers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year().to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day().to_string()),
+        );
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(answer) => JsonValue::String(answer.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for KnownAnswers {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected answer.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let puzzle = PuzzleId::new(year, day).ok_or("Expected answer.day to be part of year.")?;
+
+        Ok(KnownAnswers {
+            puzzle,
+            part_1: parse_part(json, "answer", "part_1")?,
+            part_2: parse_part(json, "answer", "part_2")?,
+        })
+    }
+}
+
+/// Parses the answer of a part of a JSON `object`, e.g. `answer` or `example`.
+/// Answers added by hand may be integers instead of strings, as long as JSON can represent them.
+pub fn parse_part(
+    json: &HashMap<String, JsonValue>,
+    object: &str,
+    key: &str,
+) -> Result<Option<String>, String> {
+    match json.get(key) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+        Some(JsonValue::Number(n)) if n.abs() > MAX_SAFE_INTEGER => Err(format!(
+            "Expected {object}.{key} to be at most 2^53 - 1, put larger answers in quotes."
+        )),
+        Some(JsonValue::Number(n)) if n.fract() == 0.0 => Ok(Some((*n as i64).to_string())),
+        _ => Err(format!(
+            "Expected {object}.{key} to be null, string or integer."
+        )),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Answers, Verdict};
+    use crate::puzzle;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "year": "2024", "day": "01", "part_1": "11", "part_2": 31 }] }"#
+            .to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(puzzle!(2024, 1), 1), Some("11"));
+        assert_eq!(answers.get(puzzle!(2024, 1), 2), Some("31"));
+        assert_eq!(answers.get(puzzle!(2024, 2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_fractional_answers() {
+        let json = r#"{ "data": [{ "year": "2024", "day": "01", "part_1": 1.5 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn errors_for_unsafe_integer_answers() {
+        let json = r#"{ "data": [{ "year": "2024", "day": "01", "part_1": 9007199254740993 }] }"#;
+        assert!(Answers::try_from(json.to_string()).is_err());
+        let json = r#"{ "data": [{ "year": "2024", "day": "01", "part_1": "9007199254740993" }] }"#;
+        let answers = Answers::try_from(json.to_string()).unwrap();
+        assert_eq!(answers.get(puzzle!(2024, 1), 1), Some("9007199254740993"));
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(puzzle!(2024, 2), 2, "4");
+        answers.set(puzzle!(2024, 1), 1, "1");
+        answers.set(puzzle!(2024, 2), 1, "3");
+        answers.set(puzzle!(2024, 2), 1, "5");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].puzzle, puzzle!(2024, 1));
+        assert_eq!(answers.get(puzzle!(2024, 2), 1), Some("5"));
+        assert_eq!(answers.get(puzzle!(2024, 2), 2), Some("4"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut answers = Answers::default();
+        answers.set(puzzle!(2024, 1), 2, "abc");
+
+        let json = JsonValue::from(answers.clone()).stringify().unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.data, answers.data);
+    }
+
+    #[test]
+    fn computes_verdicts() {
+        assert_eq!(Verdict::new(Some("1"), Some("1")), Verdict::Pass);
+        assert_eq!(Verdict::new(Some("1"), Some("2")), Verdict::Fail);
+        assert_eq!(Verdict::new(Some("1"), None), Verdict::Fail);
+        assert_eq!(Verdict::new(None, Some("1")), Verdict::Missing);
+    }
+}
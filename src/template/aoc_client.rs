@@ -0,0 +1,456 @@
+/// Native Advent of Code client, an alternative to the "aoc-cli" command-line.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use regex::{Captures, Regex};
+
+use crate::template::PuzzleId;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (+https://github.com/fspoettel/advent-of-code-rust)"
+);
+
+/// Name of the file the session cookie is read from if `AOC_SESSION` is not set.
+/// This is the same file aoc-cli uses.
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in the environment or the session file.
+    MissingSession,
+    /// The session cookie was rejected.
+    Unauthorized,
+    /// The puzzle does not exist or is not unlocked yet.
+    NotFound,
+    /// The submitted answer is wrong. Contains a hint such as "too high" if one was given.
+    WrongAnswer {
+        hint: Option<String>,
+    },
+    /// An answer was submitted too recently.
+    TooSoon {
+        wait: Duration,
+    },
+    /// The part was already solved, or can't be solved yet.
+    AlreadySolved,
+    /// The server responded with an unexpected status code.
+    BadStatus(u16),
+    /// The server response could not be understood.
+    UnexpectedResponse(String),
+    /// The request could not be sent.
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::Unauthorized => write!(f, "the session cookie was rejected."),
+            AocClientError::NotFound => write!(f, "the puzzle is not available yet."),
+            AocClientError::WrongAnswer { hint: Some(hint) } => {
+                write!(f, "that's not the right answer, your answer is {hint}.")
+            }
+            AocClientError::WrongAnswer { hint: None } => {
+                write!(f, "that's not the right answer.")
+            }
+            AocClientError::TooSoon { wait } => write!(
+                f,
+                "an answer was submitted too recently, wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            AocClientError::AlreadySolved => {
+                write!(f, "the part is already solved or not unlocked yet.")
+            }
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::UnexpectedResponse(s) => {
+                write!(f, "could not understand the server response: {s}")
+            }
+            AocClientError::Transport(e) => write!(f, "could not reach the server: {e}"),
+            AocClientError::IO(e) => write!(f, "could not write file: {e}"),
+        }
+    }
+}
+
+/// A client for the Advent of Code website, authenticated with a session cookie.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Creates a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION`, or the file `AOC_SESSION_FILE` points to
+    ///     (defaults to `~/.adventofcode.session`).
+    ///  2. the base URL is read from `AOC_BASE_URL` and defaults to `https://adventofcode.com`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => session,
+            Err(_) => session_file_path()
+                .and_then(|path| fs::read_to_string(path).ok())
+                .ok_or(AocClientError::MissingSession)?,
+        };
+
+        if session.trim().is_empty() {
+            return Err(AocClientError::MissingSession);
+        }
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetch the personal input of a puzzle.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", self.puzzle_url(puzzle)))
+    }
+
+    /// Fetch the description of a puzzle as markdown.
+    /// Contains the description of part two if part one is solved.
+    pub fn description(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&self.puzzle_url(puzzle))?;
+        let article = Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap();
+
+        let parts: Vec<String> = article
+            .captures_iter(&html)
+            .map(|c| html_to_markdown(&c[1]))
+            .collect();
+
+        if parts.is_empty() {
+            return Err(AocClientError::UnexpectedResponse(
+                "no puzzle description found.".into(),
+            ));
+        }
+
+        Ok(parts.join("\n\n"))
+    }
+
+    /// Submit the answer of a part. Returns an error if the answer is not accepted.
+    pub fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle));
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        parse_submit_response(&read_response(response)?)
+    }
+
+    /// Download the input and the description of a puzzle to the data folder.
+    pub fn download(&self, puzzle: PuzzleId) -> Result<(), AocClientError> {
+        let input_path = puzzle.data_path("inputs", "txt");
+        let puzzle_path = puzzle.data_path("puzzles", "md");
+
+        write_file(&input_path, &self.input(puzzle)?)?;
+        write_file(&puzzle_path, &self.description(puzzle)?)?;
+
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    fn puzzle_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year(),
+            puzzle.day().into_inner()
+        )
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read_response(response)
+    }
+}
+
+fn session_file_path() -> Option<PathBuf> {
+    if let Ok(path) = env::var("AOC_SESSION_FILE") {
+        return Some(PathBuf::from(path));
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(SESSION_FILE_NAME))
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocClientError::Transport(e.to_string())),
+        // NOTE: requests without a valid session are answered with 400 by the server.
+        Err(ureq::Error::Status(400 | 401 | 403, _)) => Err(AocClientError::Unauthorized),
+        Err(ureq::Error::Status(404, _)) => Err(AocClientError::NotFound),
+        Err(ureq::Error::Status(status, _)) => Err(AocClientError::BadStatus(status)),
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), AocClientError> {
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        fs::create_dir_all(parent).map_err(AocClientError::IO)?;
+    }
+    fs::write(path, contents).map_err(AocClientError::IO)
+}
+
+/// Interpret the page returned after submitting an answer.
+fn parse_submit_response(html: &str) -> Result<(), AocClientError> {
+    let article = Regex::new(r"(?s)<article>(.*?)</article>").unwrap();
+    let message = article
+        .captures(html)
+        .map(|c| strip_tags(&c[1]))
+        .ok_or_else(|| AocClientError::UnexpectedResponse("no message found.".into()))?;
+
+    if message.contains("That's the right answer") {
+        return Ok(());
+    }
+
+    if message.contains("That's not the right answer") {
+        let hint = Regex::new(r"your answer is (too high|too low)")
+            .unwrap()
+            .captures(&message)
+            .map(|c| c[1].to_string());
+        return Err(AocClientError::WrongAnswer { hint });
+    }
+
+    if message.contains("You gave an answer too recently") {
+        let wait = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait")
+            .unwrap()
+            .captures(&message)
+            .map_or(0, |c| {
+                let minutes: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+                let seconds: u64 = c[2].parse().unwrap_or(0);
+                minutes * 60 + seconds
+            });
+        return Err(AocClientError::TooSoon {
+            wait: Duration::from_secs(wait),
+        });
+    }
+
+    if message.contains("You don't seem to be solving the right level") {
+        return Err(AocClientError::AlreadySolved);
+    }
+
+    Err(AocClientError::UnexpectedResponse(message))
+}
+
+fn strip_tags(html: &str) -> String {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    unescape(tags.replace_all(html, "").trim())
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Converts the small subset of HTML used in puzzle descriptions to markdown.
+fn html_to_markdown(html: &str) -> String {
+    // Code blocks hold example inputs verbatim, so emphasis inside them is dropped, not converted.
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    let mut markdown = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>")
+        .unwrap()
+        .replace_all(html, |c: &Captures| {
+            format!("\n```\n{}```\n", tags.replace_all(&c[1], ""))
+        })
+        .to_string();
+
+    let rules = [
+        (r"(?s)<h2[^>]*>(.*?)</h2>", "## $1\n"),
+        (r"(?s)<code>(.*?)</code>", "`$1`"),
+        (r"(?s)<em[^>]*>(.*?)</em>", "*$1*"),
+        (r#"(?s)<a href="([^"]*)"[^>]*>(.*?)</a>"#, "[$2]($1)"),
+        (r"(?s)<li>(.*?)</li>", "- $1\n"),
+        (r"(?s)<p>(.*?)</p>", "$1\n\n"),
+    ];
+
+    for (pattern, replacement) in rules {
+        markdown = Regex::new(pattern)
+            .unwrap()
+            .replace_all(&markdown, replacement)
+            .to_string();
+    }
+
+    strip_tags(&markdown)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::{html_to_markdown, AocClient, AocClientError};
+    use crate::puzzle;
+
+    /// Serves a single request with the given status and body, returns the server's base URL
+    /// and a handle that yields the raw request.
+    fn mock_server(status: u16, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body_buf = vec![0; content_length];
+            reader.read_exact(&mut body_buf).unwrap();
+            request.push_str(&String::from_utf8(body_buf).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (base_url, server) = mock_server(200, "1 2 3\n");
+        let client = AocClient::new(&base_url, "secret");
+
+        assert_eq!(client.input(puzzle!(2024, 1)).unwrap(), "1 2 3\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2024/day/1/input "));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn reports_unauthorized() {
+        let (base_url, _) = mock_server(400, "Please log in.");
+        let client = AocClient::new(&base_url, "invalid");
+
+        assert!(matches!(
+            client.input(puzzle!(2024, 1)),
+            Err(AocClientError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn submits_correct_answers() {
+        let (base_url, server) = mock_server(
+            200,
+            "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        assert!(client.submit(puzzle!(2024, 1), 2, "42").is_ok());
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2024/day/1/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn reports_wrong_answers() {
+        let (base_url, _) = mock_server(
+            200,
+            "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.</p></article>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        match client.submit(puzzle!(2024, 1), 1, "42") {
+            Err(AocClientError::WrongAnswer { hint }) => {
+                assert_eq!(hint.as_deref(), Some("too high"))
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reports_wait_time() {
+        let (base_url, _) = mock_server(
+            200,
+            "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        match client.submit(puzzle!(2024, 1), 1, "42") {
+            Err(AocClientError::TooSoon { wait }) => assert_eq!(wait, Duration::from_secs(65)),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reports_solved_parts() {
+        let (base_url, _) = mock_server(
+            200,
+            "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>",
+        );
+        let client = AocClient::new(&base_url, "secret");
+
+        assert!(matches!(
+            client.submit(puzzle!(2024, 1), 1, "42"),
+            Err(AocClientError::AlreadySolved)
+        ));
+    }
+
+    #[test]
+    fn converts_descriptions() {
+        let html = "<h2>--- Day 1: Test ---</h2><p>Find the <em>sum</em> of <code>a &lt; b</code>:</p><pre><code>1\n2\n</code></pre><ul><li>one</li></ul>";
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Test ---\nFind the *sum* of `a < b`:\n\n\n```\n1\n2\n```\n- one"
+        );
+    }
+
+    #[test]
+    fn strips_emphasis_in_code_blocks() {
+        let html = "<p>For example:</p><pre><code>1 <em>2</em>\n<em>&lt;3</em>\n</code></pre>";
+        assert_eq!(
+            html_to_markdown(html),
+            "For example:\n\n\n```\n1 2\n<3\n```"
+        );
+    }
+}
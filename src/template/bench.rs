@@ -0,0 +1,213 @@
+/// Policies that control how long parts are benched and how many samples are collected.
+/// Settings are layered: command-line flags override the `BENCH` constant of a solution, which
+/// overrides `data/bench.json`, which overrides the defaults.
+use std::{cmp, collections::HashMap, fs, sync::OnceLock, time::Duration};
+
+use tinyjson::JsonValue;
+
+static BENCH_FILE_PATH: &str = "./data/bench.json";
+
+/// The bench settings of one layer, unset settings fall back to the next layer.
+///
+/// Solutions override the configured policy by defining a `BENCH` constant, e.g.
+/// `const BENCH: BenchSettings = BenchSettings { min_samples: Some(50), ..BenchSettings::NONE };`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BenchSettings {
+    /// Time spent warming up a part before samples are collected.
+    pub warmup: Option<Duration>,
+    /// Approximate time spent collecting samples of a part.
+    pub budget: Option<Duration>,
+    pub min_samples: Option<u128>,
+    pub max_samples: Option<u128>,
+}
+
+impl BenchSettings {
+    /// Settings that leave everything to the next layer.
+    pub const NONE: Self = Self {
+        warmup: None,
+        budget: None,
+        min_samples: None,
+        max_samples: None,
+    };
+
+    /// Falls back to `other` for every setting that is not set.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            warmup: self.warmup.or(other.warmup),
+            budget: self.budget.or(other.budget),
+            min_samples: self.min_samples.or(other.min_samples),
+            max_samples: self.max_samples.or(other.max_samples),
+        }
+    }
+
+    /// Rehydrate settings from the config file. If not present, every setting is unset.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(BENCH_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(BenchSettings::try_from)
+            .unwrap_or_else(|e| {
+                if fs::exists(BENCH_FILE_PATH).unwrap_or(false) {
+                    eprintln!("Ignoring {BENCH_FILE_PATH}: {e}");
+                }
+                Self::NONE
+            })
+    }
+
+    /// Resolves the settings into a policy, using the config file and the defaults for every
+    /// setting that is not set.
+    pub fn resolve(self) -> BenchPolicy {
+        static CONFIG: OnceLock<BenchSettings> = OnceLock::new();
+        let config = *CONFIG.get_or_init(BenchSettings::read_from_file);
+        BenchPolicy::from_settings(self.or(config))
+    }
+}
+
+/// How a part is benched:
+///  1. the function is warmed up for `warmup`, which also estimates the time per iteration.
+///  2. samples are collected for approx. `budget` of execution time, but at least `min_samples`
+///     and at most `max_samples`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchPolicy {
+    pub warmup: Duration,
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl BenchPolicy {
+    pub const DEFAULT: Self = Self {
+        warmup: Duration::from_millis(100),
+        budget: Duration::from_secs(1),
+        min_samples: 10,
+        max_samples: 10000,
+    };
+
+    /// Uses the defaults for every setting that is not set. Statistics need at least one sample,
+    /// and a maximum below the minimum is raised to it.
+    pub fn from_settings(settings: BenchSettings) -> Self {
+        let min_samples = cmp::max(settings.min_samples.unwrap_or(Self::DEFAULT.min_samples), 1);
+        let max_samples = cmp::max(
+            settings.max_samples.unwrap_or(Self::DEFAULT.max_samples),
+            min_samples,
+        );
+
+        Self {
+            warmup: settings.warmup.unwrap_or(Self::DEFAULT.warmup),
+            budget: settings.budget.unwrap_or(Self::DEFAULT.budget),
+            min_samples,
+            max_samples,
+        }
+    }
+
+    /// The number of samples to collect for a function that takes `iteration_time` per run.
+    pub fn samples(&self, iteration_time: Duration) -> u128 {
+        (self.budget.as_nanos() / cmp::max(iteration_time.as_nanos(), 10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+impl Default for BenchPolicy {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for BenchSettings {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = value.parse::<JsonValue>().map_err(|x| x.to_string())?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected the bench config to be a JSON object.")?;
+
+        let number = |key: &str| -> Result<Option<u64>, String> {
+            match json.get(key) {
+                None => Ok(None),
+                Some(value) => value
+                    .get::<f64>()
+                    .filter(|v| **v >= 0.0)
+                    .map(|v| Some(*v as u64))
+                    .ok_or(format!("Expected {key} to be a positive number.")),
+            }
+        };
+
+        Ok(BenchSettings {
+            warmup: number("warmup_ms")?.map(Duration::from_millis),
+            budget: number("budget_ms")?.map(Duration::from_millis),
+            min_samples: number("min_samples")?.map(u128::from),
+            max_samples: number("max_samples")?.map(u128::from),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{BenchPolicy, BenchSettings};
+
+    #[test]
+    fn layers_settings() {
+        let flags = BenchSettings {
+            budget: Some(Duration::from_millis(200)),
+            ..BenchSettings::NONE
+        };
+        let module = BenchSettings {
+            budget: Some(Duration::from_secs(5)),
+            min_samples: Some(50),
+            ..BenchSettings::NONE
+        };
+
+        let policy = BenchPolicy::from_settings(flags.or(module));
+        assert_eq!(
+            policy,
+            BenchPolicy {
+                warmup: BenchPolicy::DEFAULT.warmup,
+                budget: Duration::from_millis(200),
+                min_samples: 50,
+                max_samples: BenchPolicy::DEFAULT.max_samples,
+            }
+        );
+    }
+
+    #[test]
+    fn clamps_samples() {
+        let policy = BenchPolicy::from_settings(BenchSettings {
+            min_samples: Some(20),
+            max_samples: Some(5),
+            ..BenchSettings::NONE
+        });
+        assert_eq!((policy.min_samples, policy.max_samples), (20, 20));
+
+        let policy = BenchPolicy::DEFAULT;
+        assert_eq!(policy.samples(Duration::from_secs(2)), 10);
+        assert_eq!(policy.samples(Duration::from_millis(10)), 100);
+        assert_eq!(policy.samples(Duration::ZERO), 10000);
+    }
+
+    #[test]
+    fn parses_json() {
+        let settings = BenchSettings::try_from(
+            r#"{ "budget_ms": 500, "warmup_ms": 50, "max_samples": 1000 }"#.to_string(),
+        );
+        assert_eq!(
+            settings,
+            Ok(BenchSettings {
+                warmup: Some(Duration::from_millis(50)),
+                budget: Some(Duration::from_millis(500)),
+                min_samples: None,
+                max_samples: Some(1000),
+            })
+        );
+
+        assert!(BenchSettings::try_from(r#"{ "budget_ms": "1s" }"#.to_string()).is_err());
+        assert!(BenchSettings::try_from("[]".to_string()).is_err());
+    }
+}
@@ -1,5 +1,20 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::bench::BenchSettings;
+use crate::template::output::OutputFormat;
+use crate::template::registry::{self, Solution};
+use crate::template::run_multi::run_multi;
+use crate::template::runner::RunOptions;
+use crate::template::{all_puzzles, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(solutions: &[Solution], year: Option<Year>, jobs: usize, format: OutputFormat) {
+    let years = year.map_or_else(|| registry::years(solutions), |year| vec![year]);
+    let puzzles = years.into_iter().flat_map(all_puzzles).collect();
+    let options = RunOptions {
+        is_timed: false,
+        format,
+        quiet: false,
+        part: None,
+        memory: false,
+        bench: BenchSettings::NONE,
+    };
+    run_multi(solutions, &puzzles, &options, jobs);
 }
@@ -1,13 +1,26 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::PuzzleId;
 use std::process;
 
-pub fn handle(day: Day) {
+#[cfg(feature = "native-client")]
+pub fn handle(puzzle: PuzzleId) {
+    use crate::template::aoc_client::AocClient;
+
+    if let Err(e) = AocClient::from_env().and_then(|client| client.download(puzzle)) {
+        eprintln!("failed to download puzzle: {e}");
+        process::exit(1);
+    };
+}
+
+#[cfg(not(feature = "native-client"))]
+pub fn handle(puzzle: PuzzleId) {
+    use crate::template::aoc_cli;
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    if let Err(e) = aoc_cli::download(puzzle) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
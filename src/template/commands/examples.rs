@@ -0,0 +1,216 @@
+use std::{collections::HashMap, fs, process};
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::Verdict;
+use crate::template::examples::Examples;
+use crate::template::output::OutputFormat;
+use crate::template::registry::{self, Solution};
+use crate::template::runner::{PartResult, RunOptions};
+use crate::template::{watch, Answer, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// The outcome of running a part against one of its examples.
+struct Check<'a> {
+    file: &'a str,
+    expected: &'a str,
+    result: PartResult,
+    verdict: Verdict,
+}
+
+/// Runs solutions against the examples declared in `data/{year}/examples/{day}.json` and compares
+/// the answers with the expected ones. Exits with an error if any part does not produce them.
+pub fn handle(
+    solutions: &[Solution],
+    year: Option<Year>,
+    puzzle: Option<PuzzleId>,
+    format: OutputFormat,
+) {
+    let mut puzzles: Vec<PuzzleId> = match puzzle {
+        Some(puzzle) => vec![puzzle],
+        None => solutions
+            .iter()
+            .map(|s| s.puzzle)
+            .filter(|puzzle| year.is_none_or(|year| puzzle.year() == year))
+            .collect(),
+    };
+    puzzles.sort_unstable();
+
+    let mut manifests: Vec<Examples> = vec![];
+
+    for puzzle in puzzles {
+        match Examples::read_from_file(puzzle) {
+            // puzzles without examples are skipped.
+            Ok(examples) if examples.data.is_empty() => {}
+            Ok(examples) => manifests.push(examples),
+            Err(e) => {
+                eprintln!("Failed to read examples: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let mut checks: Vec<Check> = vec![];
+
+    for examples in &manifests {
+        let Some(solution) = registry::find(solutions, examples.puzzle) else {
+            eprintln!("No solution registered for {}.", examples.puzzle);
+            continue;
+        };
+
+        for example in &examples.data {
+            let parts: Vec<u8> = [1, 2]
+                .into_iter()
+                .filter(|part| example.expected(*part).is_some())
+                .collect();
+
+            let input_path = examples.input_path(example);
+            let Ok(input) = fs::read_to_string(&input_path) else {
+                eprintln!("Could not open example file \"{input_path}\".");
+                process::exit(1);
+            };
+
+            let options = RunOptions {
+                quiet: true,
+                // only run both parts if the example is declared for both of them.
+                part: match parts.as_slice() {
+                    [part] => Some(*part),
+                    _ => None,
+                },
+                ..RunOptions::default()
+            };
+
+            for result in solution.run(&input, &options).parts {
+                let Some(expected) = example.expected(result.part) else {
+                    continue;
+                };
+                let actual = result.answer.as_ref().map(Answer::value);
+                checks.push(Check {
+                    file: &example.file,
+                    expected,
+                    verdict: Verdict::new(Some(expected), actual.as_deref()),
+                    result,
+                });
+            }
+        }
+    }
+
+    if let Some(puzzle) = puzzle.filter(|_| checks.is_empty()) {
+        eprintln!(
+            "No examples declared for {puzzle}, add them to \"{}\".",
+            puzzle.data_path("examples", "json")
+        );
+        process::exit(1);
+    }
+
+    match format {
+        OutputFormat::Text => print_text(&checks),
+        OutputFormat::Json => {
+            let json = JsonValue::Array(checks.iter().map(to_json).collect());
+            println!("{}", json.stringify().unwrap());
+        }
+        OutputFormat::Ndjson => {
+            for check in &checks {
+                println!("{}", to_json(check).stringify().unwrap());
+            }
+        }
+    }
+
+    if checks.iter().any(|check| check.verdict == Verdict::Fail) {
+        process::exit(1);
+    }
+}
+
+/// Checks the examples of a puzzle again whenever its files change. Every check runs the binary of
+/// the solution, so it is rebuilt with the latest changes first.
+pub fn watch(puzzle: PuzzleId, format: OutputFormat) {
+    let mut args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        puzzle.to_string(),
+        "--".to_string(),
+        "--examples".to_string(),
+    ];
+
+    if !format.is_text() {
+        args.push("--format".to_string());
+        args.push(format.to_string());
+    }
+
+    watch::watch(puzzle, &[args]);
+}
+
+fn print_text(checks: &[Check]) {
+    let mut current: Option<PuzzleId> = None;
+
+    for check in checks {
+        let result = &check.result;
+
+        if current != Some(result.puzzle) {
+            if current.is_some() {
+                println!();
+            }
+            current = Some(result.puzzle);
+
+            println!(
+                "{ANSI_BOLD}{} Day {}{ANSI_RESET}",
+                result.puzzle.year(),
+                result.puzzle.day()
+            );
+            println!("-----------");
+        }
+
+        let answer = result
+            .answer
+            .as_ref()
+            .map_or_else(|| "✖".into(), Answer::value);
+        let label = format!("Part {} ({})", result.part, check.file);
+
+        match check.verdict {
+            Verdict::Pass => println!("{label}: ✔ {answer}"),
+            _ => println!("{label}: ✖ {answer} (expected {})", check.expected),
+        }
+    }
+
+    let passed = checks
+        .iter()
+        .filter(|check| check.verdict == Verdict::Pass)
+        .count();
+
+    println!();
+    println!(
+        "{ANSI_BOLD}Checked {} part(s) against examples:{ANSI_RESET} {passed} passed, {} failed.",
+        checks.len(),
+        checks.len() - passed
+    );
+}
+
+fn to_json(check: &Check) -> JsonValue {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    let result = &check.result;
+
+    map.insert(
+        "year".into(),
+        JsonValue::Number(result.puzzle.year().into_inner().into()),
+    );
+    map.insert(
+        "day".into(),
+        JsonValue::Number(result.puzzle.day().into_inner().into()),
+    );
+    map.insert("part".into(), JsonValue::Number(result.part.into()));
+    map.insert("file".into(), JsonValue::String(check.file.into()));
+    map.insert(
+        "answer".into(),
+        match &result.answer {
+            Some(answer) => JsonValue::String(answer.value()),
+            None => JsonValue::Null,
+        },
+    );
+    map.insert("expected".into(), JsonValue::String(check.expected.into()));
+    map.insert(
+        "verdict".into(),
+        JsonValue::String(check.verdict.to_string()),
+    );
+
+    JsonValue::Object(map)
+}
@@ -1,14 +1,30 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::PuzzleId;
+
+#[cfg(feature = "native-client")]
+pub fn handle(puzzle: PuzzleId) {
+    use crate::template::aoc_client::AocClient;
+
+    match AocClient::from_env().and_then(|client| client.description(puzzle)) {
+        Ok(description) => println!("{description}"),
+        Err(e) => {
+            eprintln!("failed to read puzzle: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "native-client"))]
+pub fn handle(puzzle: PuzzleId) {
+    use crate::template::aoc_cli;
 
-pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
+    if let Err(e) = aoc_cli::read(puzzle) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
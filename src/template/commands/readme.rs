@@ -0,0 +1,18 @@
+use std::process;
+
+use crate::template::readme;
+use crate::template::registry::Solution;
+
+/// Regenerates the sections of the readme from the answers, timings and puzzle descriptions.
+pub fn handle(solutions: &[Solution]) {
+    match readme::update(solutions) {
+        Ok(updated) if updated.is_empty() => {
+            println!("The readme has no sections to update.");
+        }
+        Ok(updated) => println!("Updated the readme: {}.", updated.join(", ")),
+        Err(e) => {
+            eprintln!("Failed to update the readme: {e:?}");
+            process::exit(1);
+        }
+    }
+}
@@ -1,13 +1,83 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use regex::Regex;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::examples::{self, Example, Examples, ExtractedExample};
+use crate::template::PuzzleId;
+
+const TEMPLATES_DIR: &str = "templates";
+
+/// The template a module is scaffolded from, stored as `templates/{name}.txt`.
+#[derive(Clone, Debug)]
+pub struct ModuleTemplate {
+    pub name: String,
+    /// The type of the answers returned by the parts, e.g. `u64` or `String`.
+    pub answer_type: String,
+}
+
+impl Default for ModuleTemplate {
+    fn default() -> Self {
+        Self {
+            name: "default".into(),
+            answer_type: "u64".into(),
+        }
+    }
+}
+
+impl ModuleTemplate {
+    /// Reads the source of the template, exits with the available templates if it does not exist.
+    fn source(&self) -> String {
+        let path = format!("{TEMPLATES_DIR}/{}.txt", self.name);
+        match fs::read_to_string(&path) {
+            Ok(source) => source,
+            Err(_) => {
+                eprintln!(
+                    "Unknown template \"{}\", available templates: {}.",
+                    self.name,
+                    available_templates().join(", ")
+                );
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Returns the names of the templates in `templates/`.
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            path.file_stem()?.to_str().map(String::from)
+        })
+        .collect();
+    names.sort_unstable();
+    names
+}
+
+/// Returns the title of a puzzle, e.g. "Day 13: Claw Contraption",
+/// if its description was downloaded.
+pub fn read_title(puzzle: PuzzleId) -> Option<String> {
+    let description = fs::read_to_string(puzzle.data_path("puzzles", "md")).ok()?;
+    let heading = Regex::new(r"---\s*(Day \d+: [^\n]*?)\s*\\?---").unwrap();
+    heading
+        .captures(&description)
+        .map(|captures| captures[1].to_string())
+}
+
+fn fallback_title(puzzle: PuzzleId) -> String {
+    format!("Day {}", puzzle.day().into_inner())
+}
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
@@ -20,6 +90,9 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +100,111 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Returns the literal of an expected answer in the tests of a module.
+fn answer_literal(answer: Option<&str>, answer_type: &str) -> String {
+    match answer {
+        Some(answer) if answer_type == "String" => format!("Some({answer:?}.to_string())"),
+        // other answers are kept in the example manifest only.
+        Some(answer) if answer.parse::<i64>().is_ok() => format!("Some({answer})"),
+        _ => "None".into(),
+    }
+}
+
+/// Renders the module of a puzzle. If expected answers are known, the tests assert them.
+fn render_module(
+    source: &str,
+    puzzle: PuzzleId,
+    title: &str,
+    answer_type: &str,
+    answers: [Option<&str>; 2],
+    has_part_2_example: bool,
+) -> String {
+    let mut module = source
+        .replace("%YEAR_NUMBER%", &puzzle.year().into_inner().to_string())
+        .replace("%DAY_NUMBER%", &puzzle.day().into_inner().to_string())
+        .replace("%TITLE%", title)
+        .replace("%ANSWER_TYPE%", answer_type)
+        .replace("%PART_1_ANSWER%", &answer_literal(answers[0], answer_type))
+        .replace("%PART_2_ANSWER%", &answer_literal(answers[1], answer_type));
+
+    if has_part_2_example {
+        // only the test of part two reads the separate example.
+        if let Some(index) = module.find("fn test_part_two") {
+            let (head, tail) = module.split_at(index);
+            module = head.to_string()
+                + &tail.replacen(
+                    "read_file(\"examples\", PUZZLE)",
+                    "read_file_part(\"examples\", PUZZLE, 2)",
+                    1,
+                );
+        }
+    }
+
+    module
+}
+
+/// Renders a module again if it was not changed since it was scaffolded from any template, e.g. to
+/// fill in the title of the puzzle or the expected answers. Returns whether it was updated.
+fn refresh_module(
+    puzzle: PuzzleId,
+    answer_type: &str,
+    answers: [Option<&str>; 2],
+    has_part_2_example: bool,
+) -> Result<bool, std::io::Error> {
+    let module_path = format!("src/bin/{puzzle}.rs");
+    let module = fs::read_to_string(&module_path)?;
+    let title = read_title(puzzle).unwrap_or_else(|| fallback_title(puzzle));
+    // the module may have been scaffolded before the description was downloaded.
+    let titles = [title.as_str(), &fallback_title(puzzle)];
+
+    let source = available_templates().into_iter().find_map(|name| {
+        let source = fs::read_to_string(format!("{TEMPLATES_DIR}/{name}.txt")).ok()?;
+        titles
+            .iter()
+            .any(|title| {
+                module == render_module(&source, puzzle, title, answer_type, [None, None], false)
+            })
+            .then_some(source)
+    });
+
+    let Some(source) = source else {
+        return Ok(false);
+    };
+
+    let rendered = render_module(
+        &source,
+        puzzle,
+        &title,
+        answer_type,
+        answers,
+        has_part_2_example,
+    );
+    if rendered != module {
+        fs::write(&module_path, rendered)?;
+    }
+    Ok(true)
+}
+
+/// Whether the module of a puzzle exists already.
+pub fn is_scaffolded(puzzle: PuzzleId) -> bool {
+    Path::new(&format!("src/bin/{puzzle}.rs")).exists()
+}
+
+pub fn handle(puzzle: PuzzleId, template: &ModuleTemplate, overwrite: bool) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = format!("src/bin/{puzzle}.rs");
+
+    let source = template.source();
+    let title = read_title(puzzle).unwrap_or_else(|| fallback_title(puzzle));
+    let module = render_module(
+        &source,
+        puzzle,
+        &title,
+        &template.answer_type,
+        [None, None],
+        false,
+    );
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +214,12 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!(
+                "Created module file \"{}\" from template \"{}\"",
+                &module_path, template.name
+            );
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -75,5 +248,104 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day(),
+        puzzle.year()
+    );
+}
+
+/// Fills in the example files, the example manifest and the tests of a scaffolded puzzle with the
+/// examples found in its description. The module is only updated if it was not changed yet.
+pub fn extract_examples(puzzle: PuzzleId, template: &ModuleTemplate) {
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+    let module_path = format!("src/bin/{puzzle}.rs");
+
+    let Ok(description) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not open puzzle description \"{puzzle_path}\", pass `--download` to download it."
+        );
+        process::exit(1);
+    };
+
+    let extracted = examples::extract(&description);
+
+    let Some(part_1) = extracted.iter().find(|e| e.part == 1) else {
+        eprintln!("Could not find an example in the puzzle description.");
+        return;
+    };
+    let part_2 = extracted.iter().find(|e| e.part == 2);
+    let has_part_2_example = part_2.is_some_and(|e| e.input != part_1.input);
+
+    let mut manifest = Examples {
+        puzzle,
+        data: vec![],
+    };
+
+    let mut files: Vec<(&ExtractedExample, String)> =
+        vec![(part_1, format!("{}.txt", puzzle.day()))];
+    if let Some(part_2) = part_2.filter(|_| has_part_2_example) {
+        files.push((part_2, format!("{}-2.txt", puzzle.day())));
+    }
+
+    for (example, file) in &files {
+        let example_path = format!("data/{}/examples/{file}", puzzle.year());
+        match create_file(&example_path).and_then(|mut f| f.write_all(example.input.as_bytes())) {
+            Ok(()) => println!("Extracted example file \"{example_path}\""),
+            Err(e) => {
+                eprintln!("Failed to write example file: {e}");
+                process::exit(1);
+            }
+        }
+
+        let mut entry = Example {
+            file: file.clone(),
+            part_1: None,
+            part_2: None,
+        };
+        for e in &extracted {
+            if e.input == example.input {
+                match e.part {
+                    1 => entry.part_1.clone_from(&e.answer),
+                    _ => entry.part_2.clone_from(&e.answer),
+                }
+            }
+        }
+        manifest.data.push(entry);
+    }
+
+    match manifest.store_file() {
+        Ok(()) => println!(
+            "Stored expected answers in \"{}\"",
+            puzzle.data_path("examples", "json")
+        ),
+        Err(e) => {
+            eprintln!("Failed to store example manifest: {e}");
+            process::exit(1);
+        }
+    }
+
+    let answers = [
+        part_1.answer.as_deref(),
+        part_2.and_then(|e| e.answer.as_deref()),
+    ];
+    match refresh_module(puzzle, &template.answer_type, answers, has_part_2_example) {
+        Ok(true) => println!("Updated tests of \"{module_path}\""),
+        Ok(false) => {
+            println!("Not updating tests of \"{module_path}\" because it was changed already.");
+        }
+        Err(e) => {
+            eprintln!("Failed to update module file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Fills in the title of a scaffolded puzzle once its description was downloaded. The module is
+/// only updated if it was not changed yet.
+pub fn update_title(puzzle: PuzzleId, template: &ModuleTemplate) {
+    if let Err(e) = refresh_module(puzzle, &template.answer_type, [None, None], false) {
+        eprintln!("Failed to update module file: {e}");
+        process::exit(1);
+    }
 }
@@ -1,26 +1,73 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::output::OutputFormat;
+use crate::template::{watch, PuzzleId};
+use crate::visualize::{Output, VisualizeOptions};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Runs the binary of a solution. With `watch`, the solution is checked against its examples and
+/// run again whenever its files change.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    format: OutputFormat,
+    visualize: Option<VisualizeOptions>,
+    watch: bool,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
+
+    if watch {
+        // keeps the output of every run short, build errors are still printed.
+        cmd_args.push("--quiet".to_string());
+    }
+
+    let mut features = vec![];
+    if visualize.is_some_and(|options| options.output != Output::Terminal) {
+        features.push("visualize-export");
+    }
 
     if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
+        cmd_args.extend(["--profile".to_string(), "dhat".to_string()]);
+        features.push("dhat-heap");
     } else if release {
         cmd_args.push("--release".to_string());
     }
 
+    if !features.is_empty() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push(features.join(","));
+    }
+
     cmd_args.push("--".to_string());
+    let mut examples_args = cmd_args.clone();
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+
+        if force {
+            cmd_args.push("--force".to_string());
+        }
+    }
+
+    if !format.is_text() {
+        for args in [&mut cmd_args, &mut examples_args] {
+            args.push("--format".to_string());
+            args.push(format.to_string());
+        }
+    }
+
+    if let Some(options) = visualize {
+        cmd_args.extend(options.to_args());
+    }
+
+    if watch {
+        examples_args.push("--examples".to_string());
+        watch::watch(puzzle, &[examples_args, cmd_args]);
+        return;
     }
 
     let mut cmd = Command::new("cargo")
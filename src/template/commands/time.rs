@@ -1,40 +1,328 @@
 use std::collections::HashSet;
+use std::process::{self, Command};
+use std::{env, fs};
 
+use tinyjson::JsonValue;
+
+use crate::template::bench::BenchSettings;
+use crate::template::compare::{compare, print_comparisons};
+use crate::template::history::{self, Environment, History};
+use crate::template::output::OutputFormat;
+use crate::template::registry::{self, Solution};
 use crate::template::run_multi::run_multi;
+use crate::template::runner::RunOptions;
+use crate::template::stats::format_nanos;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_puzzles, readme, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Benches solutions and appends stored benchmarks to the history of their day.
+/// If `compare_threshold` is set, the fresh timings are compared against the stored ones and the
+/// process exits with an error if any part regressed beyond the threshold.
+/// Days are benched one after another unless `jobs` is larger than `1`. With `memory`, the heap
+/// usage of every phase is measured in a separate pass afterwards, see `measure_memory`.
+/// `bench` overrides the bench settings of every day.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    solutions: &[Solution],
+    year: Option<Year>,
+    puzzle: Option<PuzzleId>,
+    run_all: bool,
+    store: bool,
+    compare_threshold: Option<f64>,
+    jobs: usize,
+    memory: bool,
+    bench: BenchSettings,
+    format: OutputFormat,
+) {
     let stored_timings = Timings::read_from_file();
 
-    let days_to_run = day.map_or_else(
+    let puzzles_to_run = puzzle.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            let years = year.map_or_else(|| registry::years(solutions), |year| vec![year]);
+
+            if compare_threshold.is_some() && !run_all {
+                // when comparing, re-bench the days that have a baseline.
+                stored_timings
+                    .data
+                    .iter()
+                    .map(|t| t.puzzle)
+                    .filter(|puzzle| years.contains(&puzzle.year()))
+                    .collect()
+            } else if run_all {
+                years.into_iter().flat_map(all_puzzles).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                years
+                    .into_iter()
+                    .flat_map(all_puzzles)
+                    .filter(|puzzle| !stored_timings.is_day_complete(*puzzle))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |puzzle| HashSet::from([puzzle]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if memory && cfg!(feature = "dhat-heap") {
+        let puzzles = match env::var(MEMORY_PUZZLES_ENV) {
+            Ok(puzzles) => puzzles.split(',').filter_map(|p| p.parse().ok()).collect(),
+            Err(_) => puzzles_to_run,
+        };
+        memory_pass(solutions, &puzzles, stored_timings, store, format);
+        return;
+    }
+
+    let options = RunOptions {
+        is_timed: true,
+        format,
+        quiet: false,
+        part: None,
+        memory: false,
+        bench,
+    };
+    let timings = run_multi(solutions, &puzzles_to_run, &options, jobs).unwrap();
+
+    let regressions = compare_threshold.map_or(0, |threshold| {
+        let comparisons = compare(&stored_timings, &timings);
+        print_comparisons(&comparisons, threshold, format.is_text())
+    });
+
+    let is_stored = store && regressions == 0;
+    if store {
+        if regressions > 0 {
+            eprintln!("Not storing benchmarks because of regressions.");
+        } else {
+            store_timings(solutions, stored_timings.merge(&timings), format);
+            if let Err(e) = History::append(&timings, &Environment::detect()) {
+                eprintln!("Failed to append to the benchmark history: {e}");
+            }
+        }
+    }
+
+    if memory {
+        let puzzles: Vec<PuzzleId> = timings.data.iter().map(|t| t.puzzle).collect();
+        measure_memory(&puzzles, is_stored, format);
+    }
+
+    if regressions > 0 {
+        process::exit(1);
+    }
+}
+
+/// Passes the puzzles of the timing pass on to the memory pass, as a comma-separated list.
+const MEMORY_PUZZLES_ENV: &str = "AOC_MEMORY_PUZZLES";
+
+/// Measuring memory needs dhat's allocator, which slows down every allocation. To keep it out of
+/// the benchmarks, the command runs again with the `dhat-heap` feature once the timing pass is
+/// done, and only measures the heap usage of `puzzles` there.
+fn measure_memory(puzzles: &[PuzzleId], store: bool, format: OutputFormat) {
+    if puzzles.is_empty() {
+        return;
+    }
 
+    let mut args = vec!["time".to_string(), "--memory".to_string()];
+    args.extend(["--format".to_string(), format.to_string()]);
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        args.push("--store".to_string());
+    }
+
+    let puzzles: Vec<String> = puzzles.iter().map(ToString::to_string).collect();
+    let status = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--features",
+            "dhat-heap",
+            "--",
+        ])
+        .args(args)
+        .env(MEMORY_PUZZLES_ENV, puzzles.join(","))
+        .status();
 
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Runs every phase of `puzzles` once and measures its heap usage. Durations measured with dhat's
+/// allocator are inflated, so only the heap usage is stored, next to the stored durations.
+fn memory_pass(
+    solutions: &[Solution],
+    puzzles: &HashSet<PuzzleId>,
+    stored_timings: Timings,
+    store: bool,
+    format: OutputFormat,
+) {
+    if format.is_text() {
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
+        println!("{ANSI_BOLD}Memory{ANSI_RESET}");
+    }
+
+    let options = RunOptions {
+        is_timed: false,
+        format,
+        quiet: false,
+        part: None,
+        memory: true,
+        bench: BenchSettings::NONE,
+    };
+    // only one dhat profiler can run at a time, so memory is measured one day after another.
+    let Some(measured) = run_multi(solutions, puzzles, &options, 1) else {
+        return;
+    };
+
+    if store {
+        store_timings(solutions, stored_timings.merge_memory(&measured), format);
+    }
+}
+
+fn store_timings(solutions: &[Solution], timings: Timings, format: OutputFormat) {
+    timings.store_file().unwrap();
+
+    match readme::update(solutions) {
+        Ok(_) => {
+            if format.is_text() {
+                println!();
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+        }
+        Err(_) => {
+            eprintln!("Failed to store updated benchmarks.");
+        }
+    }
+}
+
+/// Prints the stored benchmarks of a day, oldest first, with a sparkline of the median of every
+/// part. With `svg`, the sparklines are written to `data/{year}/history/{day}.svg` as well.
+pub fn history(puzzle: PuzzleId, svg: bool, format: OutputFormat) {
+    let history = match History::read_from_file(puzzle) {
+        Ok(history) => history,
+        Err(e) => {
+            eprintln!("Failed to read the benchmark history of {puzzle}: {e}");
+            process::exit(1);
+        }
+    };
+
+    if history.entries.is_empty() {
+        eprintln!(
+            "No benchmark history for {puzzle}. Store benchmarks with `cargo time {} --store` first.",
+            puzzle.day()
+        );
+        process::exit(1);
+    }
+
+    let entries: Vec<JsonValue> = history.entries.iter().map(JsonValue::from).collect();
+    match format {
+        OutputFormat::Text => print_history(&history),
+        OutputFormat::Json => println!("{}", JsonValue::Array(entries).stringify().unwrap()),
+        OutputFormat::Ndjson => {
+            for entry in entries {
+                println!("{}", entry.stringify().unwrap());
+            }
+        }
+    }
+
+    if svg {
+        let path = puzzle.data_path("history", "svg");
+        let image = history::svg_sparkline(&[
+            ("Part 1", "#1f77b4", history.medians(1)),
+            ("Part 2", "#ff7f0e", history.medians(2)),
+        ]);
+        match fs::write(&path, image) {
+            Ok(()) if format.is_text() => {
+                println!();
+                println!("Wrote sparklines to {path}.");
+            }
+            Ok(()) => {}
+            Err(e) => {
+                eprintln!("Failed to write {path}: {e}");
+                process::exit(1);
             }
         }
     }
 }
+
+fn print_history(history: &History) {
+    let title = format!("{} Day {}", history.puzzle.year(), history.puzzle.day());
+    println!("{title} ({} entries)", history.entries.len());
+    println!("{}", "-".repeat(title.len()));
+
+    let medians = [history.medians(1), history.medians(2)];
+
+    let mut rows = vec![[
+        "Date".to_string(),
+        "Commit".into(),
+        "Host".into(),
+        "rustc".into(),
+        "Part 1".into(),
+        "Part 2".into(),
+    ]];
+    for (i, entry) in history.entries.iter().enumerate() {
+        let environment = &entry.environment;
+        let [part_1, part_2] = medians.each_ref().map(|values| {
+            let Some(median) = values[i] else {
+                return "-".into();
+            };
+            match values[..i].iter().rev().flatten().next() {
+                Some(previous) => {
+                    format!("{} ({})", format_nanos(median), change(*previous, median))
+                }
+                None => format_nanos(median),
+            }
+        });
+        rows.push([
+            history::format_timestamp(environment.timestamp),
+            environment.revision(),
+            environment.host.clone(),
+            environment.rustc_version().into(),
+            part_1,
+            part_2,
+        ]);
+    }
+
+    let widths: Vec<usize> = (0..6)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for row in &rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        println!("{}", line.join("  ").trim_end());
+    }
+
+    println!();
+    for (part, values) in medians.iter().enumerate() {
+        let present: Vec<f64> = values.iter().flatten().copied().collect();
+        let (Some(first), Some(last)) = (present.first(), present.last()) else {
+            continue;
+        };
+        println!(
+            "Part {}: {}  {} -> {} ({})",
+            part + 1,
+            history::sparkline(values),
+            format_nanos(*first),
+            format_nanos(*last),
+            change(*first, *last)
+        );
+    }
+}
+
+/// Formats the relative change between two durations, e.g. `-12.5%`.
+fn change(previous: f64, current: f64) -> String {
+    if previous <= 0.0 {
+        return "n/a".into();
+    }
+    format!("{:+.1}%", (current - previous) / previous * 100.0)
+}
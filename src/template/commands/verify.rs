@@ -0,0 +1,158 @@
+use std::{collections::HashMap, fs, process};
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::{Answers, Verdict};
+use crate::template::output::OutputFormat;
+use crate::template::registry::{self, Solution};
+use crate::template::runner::{PartResult, RunOptions};
+use crate::template::{Answer, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Runs solutions against their real input and compares the answers with `data/answers.json`.
+/// Exits with an error if any part does not produce its known answer.
+pub fn handle(
+    solutions: &[Solution],
+    year: Option<Year>,
+    puzzle: Option<PuzzleId>,
+    run_all: bool,
+    format: OutputFormat,
+) {
+    let answers = Answers::read_from_file();
+
+    let mut puzzles: Vec<PuzzleId> = match puzzle {
+        Some(puzzle) => vec![puzzle],
+        None => solutions
+            .iter()
+            .map(|s| s.puzzle)
+            .filter(|puzzle| year.is_none_or(|year| puzzle.year() == year))
+            // when the `--all` flag is not set, only verify puzzles with known answers.
+            .filter(|puzzle| run_all || answers.data.iter().any(|a| a.puzzle == *puzzle))
+            .collect(),
+    };
+    puzzles.sort_unstable();
+
+    let options = RunOptions {
+        quiet: true,
+        ..RunOptions::default()
+    };
+
+    let mut verifications: Vec<(PartResult, Option<&str>, Verdict)> = vec![];
+
+    for puzzle in puzzles {
+        let Some(solution) = registry::find(solutions, puzzle) else {
+            eprintln!("No solution registered for {puzzle}.");
+            continue;
+        };
+
+        let input_path = puzzle.data_path("inputs", "txt");
+        let Ok(input) = fs::read_to_string(&input_path) else {
+            eprintln!("Could not open input file \"{input_path}\".");
+            continue;
+        };
+
+        for result in solution.run(&input, &options).parts {
+            let expected = answers.get(puzzle, result.part);
+            let actual = result.answer.as_ref().map(Answer::value);
+            let verdict = Verdict::new(expected, actual.as_deref());
+            verifications.push((result, expected, verdict));
+        }
+    }
+
+    match format {
+        OutputFormat::Text => print_text(&verifications),
+        OutputFormat::Json => {
+            let json = JsonValue::Array(
+                verifications
+                    .iter()
+                    .map(|(result, expected, verdict)| to_json(result, *expected, *verdict))
+                    .collect(),
+            );
+            println!("{}", json.stringify().unwrap());
+        }
+        OutputFormat::Ndjson => {
+            for (result, expected, verdict) in &verifications {
+                let json = to_json(result, *expected, *verdict);
+                println!("{}", json.stringify().unwrap());
+            }
+        }
+    }
+
+    if verifications
+        .iter()
+        .any(|(_, _, verdict)| *verdict == Verdict::Fail)
+    {
+        process::exit(1);
+    }
+}
+
+fn print_text(verifications: &[(PartResult, Option<&str>, Verdict)]) {
+    let mut current: Option<PuzzleId> = None;
+
+    for (result, expected, verdict) in verifications {
+        if current != Some(result.puzzle) {
+            if current.is_some() {
+                println!();
+            }
+            current = Some(result.puzzle);
+
+            println!(
+                "{ANSI_BOLD}{} Day {}{ANSI_RESET}",
+                result.puzzle.year(),
+                result.puzzle.day()
+            );
+            println!("-----------");
+        }
+
+        let answer = result
+            .answer
+            .as_ref()
+            .map_or_else(|| "✖".into(), Answer::value);
+        match verdict {
+            Verdict::Pass => println!("Part {}: ✔ {answer}", result.part),
+            Verdict::Fail => println!(
+                "Part {}: ✖ {answer} (expected {})",
+                result.part,
+                expected.unwrap_or_default()
+            ),
+            Verdict::Missing => println!("Part {}: ? {answer} (no known answer)", result.part),
+        }
+    }
+
+    let count = |v: Verdict| verifications.iter().filter(|(_, _, x)| *x == v).count();
+
+    println!();
+    println!(
+        "{ANSI_BOLD}Verified {} part(s):{ANSI_RESET} {} passed, {} failed, {} missing.",
+        verifications.len(),
+        count(Verdict::Pass),
+        count(Verdict::Fail),
+        count(Verdict::Missing)
+    );
+}
+
+fn to_json(result: &PartResult, expected: Option<&str>, verdict: Verdict) -> JsonValue {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+    let string_or_null = |value: Option<&str>| match value {
+        Some(value) => JsonValue::String(value.to_string()),
+        None => JsonValue::Null,
+    };
+
+    map.insert(
+        "year".into(),
+        JsonValue::Number(result.puzzle.year().into_inner().into()),
+    );
+    map.insert(
+        "day".into(),
+        JsonValue::Number(result.puzzle.day().into_inner().into()),
+    );
+    map.insert("part".into(), JsonValue::Number(result.part.into()));
+    map.insert(
+        "answer".into(),
+        string_or_null(result.answer.as_ref().map(Answer::value).as_deref()),
+    );
+    map.insert("expected".into(), string_or_null(expected));
+    map.insert("verdict".into(), JsonValue::String(verdict.to_string()));
+
+    JsonValue::Object(map)
+}
@@ -0,0 +1,284 @@
+/// Compares fresh benchmark timings against a stored baseline.
+use crate::template::stats::format_nanos;
+use crate::template::timings::Timings;
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+/// Regressions beyond this many percent fail a comparison, unless overridden with `--threshold`.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// The median duration of the parse phase or a single part before and after a change.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub puzzle: PuzzleId,
+    /// The compared part, `None` for the parse phase.
+    pub part: Option<u8>,
+    /// Median duration of the stored baseline in nanoseconds, `None` if the part is new.
+    pub baseline: Option<f64>,
+    /// Median duration of the fresh run in nanoseconds, `None` if the part failed or is missing.
+    pub current: Option<f64>,
+}
+
+impl Comparison {
+    /// Change of the median compared to the baseline in percent.
+    /// Returns `None` if there is no baseline to compare against.
+    pub fn delta_percent(&self) -> Option<f64> {
+        match (self.baseline, self.current) {
+            (Some(baseline), Some(current)) if baseline > 0.0 => {
+                Some((current - baseline) / baseline * 100.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// A part regressed if it got slower by more than `threshold` percent, or if it has a
+    /// baseline but no longer produces a result.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        match (self.baseline, self.current) {
+            (Some(_), None) => true,
+            _ => self.delta_percent().is_some_and(|delta| delta > threshold),
+        }
+    }
+}
+
+/// Compare the parse phase and every part of `current` with the same phase in `baseline`. Parts
+/// of a benched puzzle that have a baseline but failed or are missing in `current` are compared
+/// without a result.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<Comparison> {
+    let mut comparisons = vec![];
+
+    for timing in &current.data {
+        let stored = baseline.data.iter().find(|t| t.puzzle == timing.puzzle);
+
+        let phases = [
+            (None, &timing.parse),
+            (Some(1), &timing.part_1),
+            (Some(2), &timing.part_2),
+        ];
+
+        for (part, stats) in phases {
+            let baseline = stored.and_then(|t| match part {
+                None => t.parse.as_ref(),
+                Some(1) => t.part_1.as_ref(),
+                Some(_) => t.part_2.as_ref(),
+            });
+
+            // a solution that stopped parsing separately has no parse phase, but did not fail.
+            if stats.is_none() && (baseline.is_none() || part.is_none()) {
+                continue;
+            }
+
+            comparisons.push(Comparison {
+                puzzle: timing.puzzle,
+                part,
+                baseline: baseline.map(|s| s.median),
+                current: stats.as_ref().map(|s| s.median),
+            });
+        }
+    }
+
+    comparisons.sort_unstable_by_key(|c| (c.puzzle, c.part));
+    comparisons
+}
+
+/// Print a comparison table and return the number of regressed phases.
+pub fn print_comparisons(comparisons: &[Comparison], threshold: f64, is_text: bool) -> usize {
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count();
+
+    if is_text {
+        println!();
+        println!("{ANSI_BOLD}Comparison (threshold: {threshold:.1}%){ANSI_RESET}");
+        println!("-----------");
+
+        for comparison in comparisons {
+            println!("{}", format_comparison(comparison, threshold));
+        }
+    }
+
+    if regressions > 0 {
+        eprintln!("{regressions} part(s) regressed by more than {threshold:.1}%.");
+    }
+
+    regressions
+}
+
+fn format_comparison(comparison: &Comparison, threshold: f64) -> String {
+    let phase = comparison
+        .part
+        .map_or_else(|| "Parse".into(), |part| format!("Part {part}"));
+    let label = format!(
+        "{} Day {} {phase}",
+        comparison.puzzle.year(),
+        comparison.puzzle.day(),
+    );
+    let Some(current) = comparison.current.map(format_nanos) else {
+        let baseline = comparison.baseline.map(format_nanos).unwrap_or_default();
+        return format!("{label}: {baseline} -> no result REGRESSED");
+    };
+
+    match (comparison.baseline, comparison.delta_percent()) {
+        (Some(baseline), Some(delta)) => {
+            let verdict = if comparison.is_regression(threshold) {
+                " REGRESSED"
+            } else {
+                ""
+            };
+            format!(
+                "{label}: {} -> {current} ({delta:+.1}%){verdict}",
+                format_nanos(baseline)
+            )
+        }
+        _ => format!("{label}: {current} (new)"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, format_comparison, Comparison};
+    use crate::{
+        puzzle,
+        template::stats::Stats,
+        template::timings::{Timing, Timings},
+        template::PuzzleId,
+    };
+
+    fn timing(puzzle: PuzzleId, part_1: Option<f64>, part_2: Option<f64>) -> Timing {
+        Timing {
+            puzzle,
+            parse: None,
+            part_1: part_1.map(Stats::from_single),
+            part_2: part_2.map(Stats::from_single),
+            total_nanos: part_1.unwrap_or(0.0) + part_2.unwrap_or(0.0),
+            failed: vec![],
+            parse_memory: None,
+            memory: [None, None],
+        }
+    }
+
+    #[test]
+    fn compares_parts_with_baseline() {
+        let baseline = Timings {
+            data: vec![timing(puzzle!(2024, 1), Some(100.0), Some(200.0))],
+        };
+        let current = Timings {
+            data: vec![
+                timing(puzzle!(2024, 1), Some(150.0), Some(180.0)),
+                timing(puzzle!(2024, 2), Some(10.0), None),
+            ],
+        };
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 3);
+
+        assert_eq!(comparisons[0].delta_percent(), Some(50.0));
+        assert_eq!(comparisons[0].is_regression(10.0), true);
+        assert_eq!(comparisons[0].is_regression(60.0), false);
+
+        assert_eq!(comparisons[1].delta_percent(), Some(-10.0));
+        assert_eq!(comparisons[1].is_regression(10.0), false);
+
+        assert_eq!(comparisons[2].baseline, None);
+        assert_eq!(comparisons[2].is_regression(0.0), false);
+    }
+
+    #[test]
+    fn compares_parse_phase() {
+        let mut stored = timing(puzzle!(2024, 1), Some(100.0), Some(200.0));
+        stored.parse = Some(Stats::from_single(100.0));
+        let mut fresh = stored.clone();
+        fresh.parse = Some(Stats::from_single(300.0));
+
+        let comparisons = compare(
+            &Timings {
+                data: vec![stored.clone()],
+            },
+            &Timings { data: vec![fresh] },
+        );
+        assert_eq!(comparisons.len(), 3);
+        assert_eq!(comparisons[0].part, None);
+        assert_eq!(comparisons[0].delta_percent(), Some(200.0));
+        assert_eq!(comparisons[0].is_regression(10.0), true);
+
+        // a parse phase that is no longer benched separately is not a regression.
+        let comparisons = compare(
+            &Timings { data: vec![stored] },
+            &Timings {
+                data: vec![timing(puzzle!(2024, 1), Some(100.0), Some(200.0))],
+            },
+        );
+        assert!(comparisons.iter().all(|c| c.part.is_some()));
+    }
+
+    #[test]
+    fn treats_failed_parts_as_regressions() {
+        let baseline = Timings {
+            data: vec![
+                timing(puzzle!(2024, 1), Some(100.0), Some(200.0)),
+                timing(puzzle!(2024, 2), Some(100.0), Some(200.0)),
+            ],
+        };
+        let mut failed = timing(puzzle!(2024, 1), Some(100.0), None);
+        failed.failed = vec![2];
+        let current = Timings {
+            data: vec![failed, timing(puzzle!(2024, 3), Some(10.0), None)],
+        };
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons.len(), 3);
+
+        assert_eq!(
+            (comparisons[1].puzzle, comparisons[1].part),
+            (puzzle!(2024, 1), Some(2))
+        );
+        assert_eq!(comparisons[1].current, None);
+        assert_eq!(comparisons[1].is_regression(10.0), true);
+
+        // puzzles that were not benched in the fresh run are not compared.
+        assert!(comparisons.iter().all(|c| c.puzzle != puzzle!(2024, 2)));
+    }
+
+    #[test]
+    fn formats_comparisons() {
+        let regressed = Comparison {
+            puzzle: puzzle!(2024, 1),
+            part: Some(1),
+            baseline: Some(1_000_000.0),
+            current: Some(1_500_000.0),
+        };
+        assert_eq!(
+            format_comparison(&regressed, 10.0),
+            "2024 Day 01 Part 1: 1.0ms -> 1.5ms (+50.0%) REGRESSED"
+        );
+
+        let new = Comparison {
+            baseline: None,
+            ..regressed
+        };
+        assert_eq!(
+            format_comparison(&new, 10.0),
+            "2024 Day 01 Part 1: 1.5ms (new)"
+        );
+
+        let failed = Comparison {
+            current: None,
+            ..regressed
+        };
+        assert_eq!(
+            format_comparison(&failed, 10.0),
+            "2024 Day 01 Part 1: 1.0ms -> no result REGRESSED"
+        );
+
+        let parse = Comparison {
+            part: None,
+            ..regressed
+        };
+        assert_eq!(
+            format_comparison(&parse, 10.0),
+            "2024 Day 01 Parse: 1.0ms -> 1.5ms (+50.0%) REGRESSED"
+        );
+    }
+}
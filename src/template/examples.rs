@@ -0,0 +1,367 @@
+/// Example inputs of a puzzle and the answers they are expected to produce.
+use std::{collections::HashMap, fs, io::Error, path::Path, str::FromStr};
+
+use regex::Regex;
+use tinyjson::JsonValue;
+
+use crate::template::{answers::parse_part, PuzzleId};
+
+/// A single example input, stored in `data/{year}/examples/{file}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub file: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Example {
+    /// Returns the expected answer of a part,
+    /// `None` if the part is not checked against this example.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The examples of a puzzle, declared in the manifest `data/{year}/examples/{day}.json`.
+#[derive(Clone, Debug)]
+pub struct Examples {
+    pub puzzle: PuzzleId,
+    pub data: Vec<Example>,
+}
+
+impl Examples {
+    fn file_path(puzzle: PuzzleId) -> String {
+        puzzle.data_path("examples", "json")
+    }
+
+    /// Rehydrate the examples of a puzzle from its manifest. If not present, returns no examples.
+    pub fn read_from_file(puzzle: PuzzleId) -> Result<Self, String> {
+        let path = Self::file_path(puzzle);
+
+        let data = match fs::read_to_string(&path) {
+            Ok(value) => parse_examples(value).map_err(|e| format!("{path}: {e}"))?,
+            Err(_) => vec![],
+        };
+
+        Ok(Self { puzzle, data })
+    }
+
+    /// Dehydrate the examples to their manifest.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let path = Self::file_path(self.puzzle);
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Returns the path of an example input.
+    pub fn input_path(&self, example: &Example) -> String {
+        format!("data/{}/examples/{}", self.puzzle.year(), example.file)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An example input and its expected answer, as found in the description of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtractedExample {
+    pub part: u8,
+    pub input: String,
+    pub answer: Option<String>,
+}
+
+const PART_TWO_MARKER: &str = "--- Part Two ---";
+
+/// Extracts the example inputs and answers from a puzzle description, as downloaded to
+/// `data/{year}/puzzles/{day}.md`. Part two reuses the example of part one unless it
+/// introduces an example of its own.
+///
+/// This relies on the conventions of puzzle descriptions and should be reviewed:
+///  1. the example input is the first code block after a paragraph mentioning an example.
+///  2. the expected answer is the last emphasized code (e.g. `*143*`) of the part.
+pub fn extract(description: &str) -> Vec<ExtractedExample> {
+    let (part_1, part_2) = match description.find(PART_TWO_MARKER) {
+        Some(index) => (&description[..index], Some(&description[index..])),
+        None => (description, None),
+    };
+
+    let mut examples = vec![];
+
+    let blocks = code_blocks(part_1);
+    let Some(input) = blocks
+        .iter()
+        .find(|(paragraph, _)| introduces_example(paragraph))
+        .or(blocks.first())
+        .map(|(_, input)| input.clone())
+    else {
+        return examples;
+    };
+
+    examples.push(ExtractedExample {
+        part: 1,
+        input: input.clone(),
+        answer: last_answer(part_1),
+    });
+
+    if let Some(part_2) = part_2 {
+        let input = code_blocks(part_2)
+            .into_iter()
+            .find(|(paragraph, _)| introduces_example(paragraph) && !refers_back(paragraph))
+            .map_or(input, |(_, input)| input);
+
+        examples.push(ExtractedExample {
+            part: 2,
+            input,
+            answer: last_answer(part_2),
+        });
+    }
+
+    examples
+}
+
+/// Returns every code block of a description along with the paragraph preceding it.
+fn code_blocks(description: &str) -> Vec<(String, String)> {
+    let block = Regex::new(r"(?s)```[^\n]*\n(.*?)```|<pre><code>(.*?)</code></pre>").unwrap();
+
+    let mut blocks = vec![];
+    let mut last_end = 0;
+
+    for captures in block.captures_iter(description) {
+        let whole = captures.get(0).unwrap();
+        let content = captures.get(1).or(captures.get(2)).unwrap().as_str();
+
+        let paragraph = description[last_end..whole.start()]
+            .trim()
+            .rsplit("\n\n")
+            .next()
+            .unwrap_or_default()
+            .to_string();
+
+        blocks.push((paragraph, content.to_string()));
+        last_end = whole.end();
+    }
+
+    blocks
+}
+
+fn introduces_example(paragraph: &str) -> bool {
+    paragraph.to_lowercase().contains("example")
+}
+
+/// Whether a paragraph talks about an example that was shown before, e.g. "in the above example".
+fn refers_back(paragraph: &str) -> bool {
+    let paragraph = paragraph.to_lowercase();
+    ["above", "same", "previous", "again"]
+        .iter()
+        .any(|word| paragraph.contains(word))
+}
+
+/// Returns the last emphasized code of a description, e.g. `*143*`.
+fn last_answer(description: &str) -> Option<String> {
+    let answer = Regex::new(
+        r"(?s)`\*([^`*]+)\*`|\*`([^`*]+)`\*|<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>",
+    )
+    .unwrap();
+
+    answer
+        .captures_iter(description)
+        .last()
+        .and_then(|c| c.iter().skip(1).flatten().next())
+        .map(|m| m.as_str().trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Examples> for JsonValue {
+    fn from(value: &Examples) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+fn parse_examples(value: String) -> Result<Vec<Example>, String> {
+    let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+    json.get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?
+        .get("data")
+        .ok_or("expected JSON document to have key `data`.")?
+        .get::<Vec<JsonValue>>()
+        .ok_or("expected `json.data` to be an array.")?
+        .iter()
+        .map(Example::try_from)
+        .collect()
+}
+
+impl From<&Example> for JsonValue {
+    fn from(value: &Example) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("file".into(), JsonValue::String(value.file.clone()));
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(answer) => JsonValue::String(answer.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let file = json
+            .get("file")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected example.file to be a string.")?
+            .clone();
+
+        Ok(Example {
+            file,
+            part_1: parse_part(json, "example", "part_1")?,
+            part_2: parse_part(json, "example", "part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{extract, parse_examples, Example, Examples, ExtractedExample};
+    use crate::puzzle;
+
+    #[test]
+    fn handles_json_examples() {
+        let json = r#"{ "data": [{ "file": "03.txt", "part_1": 161 }, { "file": "03-2.txt", "part_1": null, "part_2": "48" }] }"#
+            .to_string();
+        let examples = parse_examples(json).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].expected(1), Some("161"));
+        assert_eq!(examples[0].expected(2), None);
+        assert_eq!(examples[1].file, "03-2.txt");
+        assert_eq!(examples[1].expected(1), None);
+        assert_eq!(examples[1].expected(2), Some("48"));
+    }
+
+    #[test]
+    fn errors_for_malformed_examples() {
+        assert!(parse_examples(r#"{ "data": [{ "part_1": "1" }] }"#.into()).is_err());
+        assert!(
+            parse_examples(r#"{ "data": [{ "file": "01.txt", "part_2": 1.5 }] }"#.into()).is_err()
+        );
+    }
+
+    const DESCRIPTION: &str = "\
+## --- Day 1: Test ---
+The numbers are on a list.
+
+For example:
+
+```
+3   4
+4   3
+```
+
+In the example above, this is `*11*`.
+
+## --- Part Two ---
+The list is the same as in the above example:
+
+```
+3   4
+```
+
+So, the similarity score is `*31*`.
+";
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(
+            extract(DESCRIPTION),
+            vec![
+                ExtractedExample {
+                    part: 1,
+                    input: "3   4\n4   3\n".into(),
+                    answer: Some("11".into()),
+                },
+                ExtractedExample {
+                    part: 2,
+                    input: "3   4\n4   3\n".into(),
+                    answer: Some("31".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_examples_of_part_two() {
+        let description = DESCRIPTION.replace(
+            "The list is the same as in the above example:",
+            "Consider this example:",
+        );
+        let examples = extract(&description);
+        assert_eq!(examples[1].input, "3   4\n");
+        assert_eq!(examples[1].answer, Some("31".into()));
+    }
+
+    #[test]
+    fn extracts_examples_from_html() {
+        let description = "<p>For example:</p><pre><code>1\n2\n</code></pre><p>The answer is <code><em>3</em></code>.</p>";
+        assert_eq!(
+            extract(description),
+            vec![ExtractedExample {
+                part: 1,
+                input: "1\n2\n".into(),
+                answer: Some("3".into()),
+            }]
+        );
+        assert_eq!(extract("No code here."), vec![]);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let examples = Examples {
+            puzzle: puzzle!(2024, 3),
+            data: vec![Example {
+                file: "03-2.txt".into(),
+                part_1: None,
+                part_2: Some("48".into()),
+            }],
+        };
+
+        let json = JsonValue::from(&examples).stringify().unwrap();
+        assert_eq!(parse_examples(json).unwrap(), examples.data);
+        assert_eq!(
+            examples.input_path(&examples.data[0]),
+            "data/2024/examples/03-2.txt"
+        );
+    }
+}
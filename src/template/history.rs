@@ -0,0 +1,435 @@
+/// Append-only history of stored benchmarks, kept as one JSON object per line in
+/// `data/{year}/history/{day}.jsonl`. Every entry records the commit, compiler and host it was
+/// benched with, so changes in the timings of a day can be traced back to their cause.
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Write as _,
+    fs::{self, OpenOptions},
+    io::{Error, ErrorKind, Write},
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::PuzzleId;
+
+/// Where and when benchmarks were taken.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    /// The abbreviated hash of the checked out commit, `None` outside of a git repository.
+    pub commit: Option<String>,
+    /// Whether `src/` had uncommitted changes.
+    pub dirty: bool,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The version of the compiler that built the solutions, e.g. `rustc 1.83.0 (...)`.
+    pub rustc: String,
+    pub host: String,
+}
+
+impl Environment {
+    /// Detects the environment of the running process.
+    pub fn detect() -> Self {
+        let commit = git(&["rev-parse", "--short", "HEAD"]);
+        let dirty = git(&["status", "--porcelain", "--untracked-files=no", "--", "src"])
+            .is_some_and(|status| !status.is_empty());
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        let host = env::var("HOSTNAME")
+            .or_else(|_| env::var("COMPUTERNAME"))
+            .ok()
+            .or_else(|| command_output("hostname", &[]))
+            .unwrap_or_else(|| "unknown".into());
+
+        Self {
+            commit,
+            dirty,
+            timestamp,
+            rustc: env!("AOC_RUSTC_VERSION").into(),
+            host,
+        }
+    }
+
+    /// The commit, marked with a `*` if the solutions had uncommitted changes.
+    pub fn revision(&self) -> String {
+        let commit = self.commit.as_deref().unwrap_or("-");
+        if self.dirty {
+            format!("{commit}*")
+        } else {
+            commit.into()
+        }
+    }
+
+    /// The version number of the compiler, e.g. `1.83.0`.
+    pub fn rustc_version(&self) -> &str {
+        self.rustc.split_whitespace().nth(1).unwrap_or(&self.rustc)
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    command_output("git", args)
+}
+
+/// Returns the trimmed output of a command if it succeeded.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+/// A stored benchmark of a puzzle.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    pub environment: Environment,
+    pub timing: Timing,
+}
+
+/// The stored benchmarks of a puzzle, oldest first.
+#[derive(Clone, Debug)]
+pub struct History {
+    pub puzzle: PuzzleId,
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    fn file_path(puzzle: PuzzleId) -> String {
+        puzzle.data_path("history", "jsonl")
+    }
+
+    /// Rehydrate the history of a puzzle. If not present, returns an empty history.
+    pub fn read_from_file(puzzle: PuzzleId) -> Result<Self, String> {
+        let content = match fs::read_to_string(Self::file_path(puzzle)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.to_string()),
+        };
+
+        let entries = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                line.parse::<JsonValue>()
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| HistoryEntry::try_from(&json))
+                    .map_err(|e| format!("line {}: {e}", i + 1))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { puzzle, entries })
+    }
+
+    /// Appends every timing to the history of its puzzle. Existing entries are never rewritten.
+    pub fn append(timings: &Timings, environment: &Environment) -> Result<(), Error> {
+        for timing in &timings.data {
+            let path = Self::file_path(timing.puzzle);
+            if let Some(parent) = Path::new(&path).parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            let entry = HistoryEntry {
+                environment: environment.clone(),
+                timing: timing.clone(),
+            };
+            let line = JsonValue::from(&entry)
+                .stringify()
+                .map_err(|e| Error::other(e.to_string()))?;
+
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{line}")?;
+        }
+        Ok(())
+    }
+
+    /// The median of a part in every entry, `None` where the part was not benched.
+    pub fn medians(&self, part: u8) -> Vec<Option<f64>> {
+        self.entries
+            .iter()
+            .map(|entry| match part {
+                1 => entry.timing.part_1.map(|stats| stats.median),
+                _ => entry.timing.part_2.map(|stats| stats.median),
+            })
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders values as a line of block characters, from the lowest to the highest value.
+/// Missing values are rendered as spaces.
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    let (min, max) = bounds(values);
+
+    values
+        .iter()
+        .map(|value| match value {
+            None => ' ',
+            Some(value) if max > min => {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let level = ((value - min) / (max - min) * 7.0).round() as usize;
+                SPARKS[level]
+            }
+            Some(_) => SPARKS[3],
+        })
+        .collect()
+}
+
+const SVG_STEP: f64 = 24.0;
+const SVG_HEIGHT: f64 = 64.0;
+const SVG_PADDING: f64 = 4.0;
+
+/// Renders every series as a line of an SVG image. Each series is scaled to its own range, so
+/// parts of very different durations can be compared by their trend.
+pub fn svg_sparkline(series: &[(&str, &str, Vec<Option<f64>>)]) -> String {
+    let len = series
+        .iter()
+        .map(|(_, _, values)| values.len())
+        .max()
+        .unwrap_or(0);
+    #[allow(clippy::cast_precision_loss)]
+    let width = len.saturating_sub(1).max(1) as f64 * SVG_STEP + 2.0 * SVG_PADDING;
+    let height = SVG_HEIGHT + 2.0 * SVG_PADDING;
+
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )
+    .unwrap();
+
+    for (name, color, values) in series {
+        let (min, max) = bounds(values);
+        let points: Vec<(f64, f64)> = values
+            .iter()
+            .enumerate()
+            .filter_map(|(i, value)| {
+                let value = (*value)?;
+                let y = if max > min {
+                    (max - value) / (max - min) * SVG_HEIGHT
+                } else {
+                    SVG_HEIGHT / 2.0
+                };
+                #[allow(clippy::cast_precision_loss)]
+                Some((i as f64 * SVG_STEP + SVG_PADDING, y + SVG_PADDING))
+            })
+            .collect();
+
+        let coordinates: Vec<String> = points
+            .iter()
+            .map(|(x, y)| format!("{x:.1},{y:.1}"))
+            .collect();
+        writeln!(
+            svg,
+            r#"  <g stroke="{color}" fill="{color}"><title>{name}</title>"#
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"    <polyline fill="none" stroke-width="2" points="{}"/>"#,
+            coordinates.join(" ")
+        )
+        .unwrap();
+        for (x, y) in points {
+            writeln!(svg, r#"    <circle cx="{x:.1}" cy="{y:.1}" r="2.5"/>"#).unwrap();
+        }
+        writeln!(svg, "  </g>").unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn bounds(values: &[Option<f64>]) -> (f64, f64) {
+    values
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+            (min.min(*value), max.max(*value))
+        })
+}
+
+/// Formats seconds since the unix epoch as a UTC date and time, e.g. `2024-12-06 05:30`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    #[allow(clippy::cast_possible_wrap)]
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // converts days since the epoch to a civil date, see
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &HistoryEntry) -> Self {
+        let environment = &value.environment;
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "commit".into(),
+            environment
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(environment.dirty));
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(environment.timestamp as f64),
+        );
+        map.insert("rustc".into(), JsonValue::String(environment.rustc.clone()));
+        map.insert("host".into(), JsonValue::String(environment.host.clone()));
+        map.insert("timing".into(), JsonValue::from(&value.timing));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected entry.{key} to be a string."))
+        };
+
+        let commit = match json.get("commit") {
+            Some(JsonValue::Null) | None => None,
+            Some(_) => Some(string("commit")?),
+        };
+
+        let dirty = json
+            .get("dirty")
+            .and_then(|v| v.get::<bool>())
+            .copied()
+            .ok_or("Expected entry.dirty to be a boolean.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let timing = json
+            .get("timing")
+            .ok_or("Expected entry.timing to be present.")
+            .map_err(String::from)
+            .and_then(Timing::try_from)?;
+
+        Ok(HistoryEntry {
+            environment: Environment {
+                commit,
+                dirty,
+                timestamp,
+                rustc: string("rustc")?,
+                host: string("host")?,
+            },
+            timing,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use crate::{
+        puzzle,
+        template::{stats::Stats, timings::Timing},
+    };
+
+    use super::{format_timestamp, sparkline, svg_sparkline, Environment, HistoryEntry};
+
+    #[test]
+    fn renders_sparklines() {
+        assert_eq!(
+            sparkline(&[Some(8.0), Some(1.0), None, Some(4.5), Some(1.0)]),
+            "█▁ ▅▁"
+        );
+        assert_eq!(sparkline(&[Some(2.0), Some(2.0)]), "▄▄");
+        assert_eq!(sparkline(&[]), "");
+
+        let svg = svg_sparkline(&[("Part 1", "red", vec![Some(2.0), None, Some(1.0)])]);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(r#"points="4.0,4.0 52.0,68.0""#));
+        assert_eq!(svg.matches("<circle").count(), 2);
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_463_000), "2024-12-06 05:30");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn converts_json() {
+        let entry = HistoryEntry {
+            environment: Environment {
+                commit: Some("a1b2c3d".into()),
+                dirty: true,
+                timestamp: 1_733_463_000,
+                rustc: "rustc 1.83.0 (90b35a623 2024-11-26)".into(),
+                host: "workstation".into(),
+            },
+            timing: Timing {
+                puzzle: puzzle!(2024, 6),
+                parse: None,
+                part_1: Some(Stats::from_single(1000.0)),
+                part_2: None,
+                total_nanos: 1000.0,
+                failed: vec![],
+                parse_memory: None,
+                memory: [None, None],
+            },
+        };
+
+        let json = JsonValue::from(&entry);
+        let parsed = HistoryEntry::try_from(&json).unwrap();
+        assert_eq!(parsed.environment, entry.environment);
+        assert_eq!(parsed.environment.revision(), "a1b2c3d*");
+        assert_eq!(parsed.environment.rustc_version(), "1.83.0");
+        assert_eq!(parsed.timing.puzzle, puzzle!(2024, 6));
+        assert_eq!(parsed.timing.part_1, entry.timing.part_1);
+        assert!(HistoryEntry::try_from(&JsonValue::Null).is_err());
+    }
+}
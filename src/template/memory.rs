@@ -0,0 +1,137 @@
+/// Heap usage of solution parts. Measuring it requires the `dhat-heap` feature, which installs
+/// dhat's allocator to count every allocation.
+use std::collections::HashMap;
+
+use tinyjson::JsonValue;
+
+/// The heap usage of a single run of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Memory {
+    /// Bytes allocated over the whole run.
+    pub total_bytes: u64,
+    /// The most bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+    pub allocations: u64,
+}
+
+/// Runs `func` and measures its heap usage. If `profile` is set, dhat's full profile is written
+/// to that file as well, e.g. `dhat-heap-1.json` for part 1 with `cargo solve --dhat`.
+#[cfg(feature = "dhat-heap")]
+pub fn measure<T>(func: impl FnOnce() -> T, profile: Option<&str>) -> (T, Option<Memory>) {
+    let _profiler = match profile {
+        Some(file_name) => dhat::Profiler::builder().file_name(file_name).build(),
+        None => dhat::Profiler::builder().testing().build(),
+    };
+
+    let result = func();
+    let stats = dhat::HeapStats::get();
+
+    let memory = Memory {
+        total_bytes: stats.total_bytes,
+        peak_bytes: stats.max_bytes as u64,
+        allocations: stats.total_blocks,
+    };
+    (result, Some(memory))
+}
+
+/// Runs `func`, heap usage is only measured with the `dhat-heap` feature.
+#[cfg(not(feature = "dhat-heap"))]
+pub fn measure<T>(func: impl FnOnce() -> T, _profile: Option<&str>) -> (T, Option<Memory>) {
+    (func(), None)
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64;
+    let mut unit = "B";
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{value:.1} {unit}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Memory> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Memory) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Memory {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        let field = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(Memory {
+            total_bytes: field("total_bytes")?,
+            peak_bytes: field("peak_bytes")?,
+            allocations: field("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_bytes, Memory};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn converts_json() {
+        let memory = Memory {
+            total_bytes: 4096,
+            peak_bytes: 1024,
+            allocations: 12,
+        };
+        let json = JsonValue::from(&memory);
+        assert_eq!(Memory::try_from(&json), Ok(memory));
+        assert!(Memory::try_from(&JsonValue::Null).is_err());
+    }
+}
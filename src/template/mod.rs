@@ -1,15 +1,40 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+#[cfg(feature = "native-client")]
+pub mod aoc_client;
+pub mod bench;
 pub mod commands;
+pub mod compare;
+pub mod memory;
+pub mod output;
+pub mod registry;
 pub mod runner;
+pub mod stats;
+pub mod watch;
 
+pub use answer::*;
 pub use day::*;
+pub use puzzle::*;
+pub use year::*;
 
+mod answer;
+mod answers;
 mod day;
+mod examples;
+mod history;
+mod puzzle;
+mod readme;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod year;
+
+/// Counts allocations when profiling with dhat, for the main binary as well as for solutions.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,52 +42,164 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(puzzle.data_part_path(folder, part, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `PUZZLE` and sets up the input and runner for each part.
+/// Also creates the constant `SOLUTION`, which registers the parts with the main binary.
+///
+/// The first two parameters are the year and the day of the puzzle, e.g. `solution!(2024, 1)`.
+/// The optional, third parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Solutions can parse their input once and share it between both parts by passing a parse
+/// function and the type it returns, e.g. `solution!(2024, 1, parse_input -> Input)`.
+/// The parts then take `&Input` instead of `&str`, and parsing is timed as a separate phase.
+/// The parse function may also return `Result<Input, E>` with `E: Display`, if it returns an
+/// error, the error is reported by the parse phase and both parts are skipped.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Solutions can tune how they are benched by defining a `BENCH` constant, e.g.
+/// `const BENCH: BenchSettings = BenchSettings { min_samples: Some(50), ..BenchSettings::NONE };`
+/// A `COMPLEXITY` constant, e.g. `const COMPLEXITY: Option<&str> = Some("O(n log n)");`, is
+/// listed in the progress table of the readme.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($year:expr, $day:expr) => {
+        $crate::solution!(@impl $year, $day, [part_one, 1] [part_two, 2]);
+    };
+    ($year:expr, $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, [part_one, 1]);
+    };
+    ($year:expr, $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, [part_two, 2]);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    ($year:expr, $day:expr, $parse:ident -> $parsed:ty) => {
+        $crate::solution!(@parsed $year, $day, $parse, $parsed, [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    ($year:expr, $day:expr, $parse:ident -> $parsed:ty, 1) => {
+        $crate::solution!(@parsed $year, $day, $parse, $parsed, [part_one, 1]);
+    };
+    ($year:expr, $day:expr, $parse:ident -> $parsed:ty, 2) => {
+        $crate::solution!(@parsed $year, $day, $parse, $parsed, [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+    (@impl $year:expr, $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $year, $day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The registry entry of the current puzzle.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                puzzle: PUZZLE,
+                runner: |input, options| {
+                    use $crate::template::runner::*;
+                    let options = &options.with_bench(BENCH);
+                    let mut parts = vec![];
+                    $(
+                        if options.runs_part($part) {
+                            parts.push(execute_part($func, input, PUZZLE, $part, options));
+                        }
+                    )*
+                    $crate::template::registry::SolutionResult { parse: None, parts }
+                },
+                complexity: COMPLEXITY,
+            };
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = RunOptions::from_args().with_bench(BENCH);
+            if check_examples_from_args(&SOLUTION, &options) {
+                return;
+            }
+            let input = $crate::template::read_file("inputs", PUZZLE);
+            $crate::visualize::init_from_args();
+            let results = [$( run_part($func, &input, PUZZLE, $part, &options), )*];
+            $crate::template::output::print_report(&results, options.format);
+        }
+    };
+
+    (@parsed $year:expr, $day:expr, $parse:ident, $parsed:ty, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $year, $day);
+
+        /// The registry entry of the current puzzle.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                puzzle: PUZZLE,
+                runner: |input, options| {
+                    use $crate::template::runner::*;
+                    let options = &options.with_bench(BENCH);
+                    let (parsed, parse): (Option<$parsed>, _) =
+                        execute_parse($parse, input, PUZZLE, options);
+                    let mut parts = vec![];
+                    $(
+                        if options.runs_part($part) {
+                            parts.push(match &parsed {
+                                Some(parsed) => {
+                                    let func = |input: &$parsed| $func(input);
+                                    execute_part(func, parsed, PUZZLE, $part, options)
+                                }
+                                None => skip_part(PUZZLE, $part, options),
+                            });
+                        }
+                    )*
+                    $crate::template::registry::SolutionResult { parse: Some(parse), parts }
+                },
+                complexity: COMPLEXITY,
+            };
+
+        fn main() {
+            use $crate::template::runner::*;
+            let options = RunOptions::from_args().with_bench(BENCH);
+            if check_examples_from_args(&SOLUTION, &options) {
+                return;
+            }
+            let input = $crate::template::read_file("inputs", PUZZLE);
+            $crate::visualize::init_from_args();
+            let (parsed, _): (Option<$parsed>, _) =
+                execute_parse($parse, &input, PUZZLE, &options);
+            let results = [$(
+                match &parsed {
+                    Some(parsed) => {
+                        run_part(|input: &$parsed| $func(input), parsed, PUZZLE, $part, &options)
+                    }
+                    None => skip_part(PUZZLE, $part, &options),
+                },
+            )*];
+            $crate::template::output::print_report(&results, options.format);
+        }
+    };
+
+    (@common $year:expr, $day:expr) => {
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::puzzle!($year, $day);
+
+        // constants defined by the solution shadow the defaults of the glob import.
+        #[allow(unused_imports)]
+        use self::solution_defaults::*;
+
+        #[doc(hidden)]
+        mod solution_defaults {
+            /// The bench settings of the solution, falls back to the configured policy.
+            #[allow(dead_code)]
+            pub const BENCH: $crate::template::bench::BenchSettings =
+                $crate::template::bench::BenchSettings::NONE;
+
+            /// A note on the complexity of the solution, shown in the readme.
+            #[allow(dead_code)]
+            pub const COMPLEXITY: Option<&str> = None;
         }
     };
 }
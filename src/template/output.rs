@@ -0,0 +1,230 @@
+/// Machine-readable output of solution results.
+use std::{collections::HashMap, error::Error, fmt::Display, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::runner::PartResult;
+
+/// The format results of `solve`, `all` and `time` are written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable, colored text.
+    #[default]
+    Text,
+    /// A single JSON array containing every part, written after all parts ran.
+    Json,
+    /// One JSON object per line, written as soon as a part finishes.
+    Ndjson,
+}
+
+impl OutputFormat {
+    pub fn is_text(self) -> bool {
+        self == OutputFormat::Text
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+        })
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "ndjson" => Ok(OutputFormat::Ndjson),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `text`, `json` or `ndjson`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Print the result of a single part if results are streamed.
+pub fn print_part(result: &PartResult, format: OutputFormat) {
+    if format == OutputFormat::Ndjson {
+        println!("{}", JsonValue::from(result).stringify().unwrap());
+    }
+}
+
+/// Print the results of all parts if results are written as a single document.
+pub fn print_report(results: &[PartResult], format: OutputFormat) {
+    if format == OutputFormat::Json {
+        let json = JsonValue::Array(results.iter().map(JsonValue::from).collect());
+        println!("{}", json.stringify().unwrap());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(value.puzzle.year().into_inner().into()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::Number(value.puzzle.day().into_inner().into()),
+        );
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.value()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "answer_type".into(),
+            match &value.answer {
+                Some(answer) => JsonValue::String(answer.kind().into()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status().to_string()),
+        );
+        if let Some(chain) = &value.error {
+            map.insert(
+                "error".into(),
+                JsonValue::Array(chain.iter().cloned().map(JsonValue::String).collect()),
+            );
+        }
+        if let Some(stats) = &value.stats {
+            map.insert("stats".into(), JsonValue::from(stats));
+        }
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::OutputFormat;
+    use crate::{
+        puzzle,
+        template::{runner::PartResult, Answer},
+    };
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
+        assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "ndjson".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Ndjson
+        );
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn serializes_part_results() {
+        let result = PartResult {
+            puzzle: puzzle!(2024, 1),
+            part: 2,
+            answer: Some(Answer::Integer(42)),
+            error: None,
+            duration: Duration::from_nanos(1500),
+            samples: 10,
+            stats: None,
+            memory: None,
+        };
+
+        let json = JsonValue::from(&result);
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["year"], JsonValue::Number(2024.0));
+        assert_eq!(map["day"], JsonValue::Number(1.0));
+        assert_eq!(map["part"], JsonValue::Number(2.0));
+        assert_eq!(map["answer"], JsonValue::String("42".into()));
+        assert_eq!(map["answer_type"], JsonValue::String("integer".into()));
+        assert_eq!(map["duration_nanos"], JsonValue::Number(1500.0));
+        assert_eq!(map["samples"], JsonValue::Number(10.0));
+        assert_eq!(map["status"], JsonValue::String("solved".into()));
+    }
+
+    #[test]
+    fn serializes_unsolved_parts() {
+        let result = PartResult {
+            puzzle: puzzle!(2024, 1),
+            part: 1,
+            answer: None,
+            error: None,
+            duration: Duration::from_nanos(10),
+            samples: 1,
+            stats: None,
+            memory: None,
+        };
+
+        let json = JsonValue::from(&result);
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["answer"], JsonValue::Null);
+        assert_eq!(map["status"], JsonValue::String("unsolved".into()));
+    }
+
+    #[test]
+    fn serializes_failed_parts() {
+        let result = PartResult {
+            puzzle: puzzle!(2024, 1),
+            part: 1,
+            answer: None,
+            error: Some(vec!["could not parse input".into(), "invalid digit".into()]),
+            duration: Duration::from_nanos(10),
+            samples: 1,
+            stats: None,
+            memory: None,
+        };
+
+        let json = JsonValue::from(&result);
+        let map = json.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["status"], JsonValue::String("failed".into()));
+        assert_eq!(
+            map["error"],
+            JsonValue::Array(vec![
+                JsonValue::String("could not parse input".into()),
+                JsonValue::String("invalid digit".into())
+            ])
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{all_days, Day, Year};
+
+/// Identifies a single puzzle of advent of code by its year and day.
+///
+/// # Display
+/// This value displays as `{year}-{day}`, which is also the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2024).unwrap(), Day::new(8).unwrap()).unwrap();
+/// assert_eq!(puzzle.to_string(), "2024-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    year: Year,
+    day: Day,
+}
+
+impl PuzzleId {
+    /// Creates a [`PuzzleId`] if the day is part of the year's event, returns [`None`] otherwise.
+    pub fn new(year: Year, day: Day) -> Option<Self> {
+        if day.into_inner() > year.last_day() {
+            return None;
+        }
+        Some(Self { year, day })
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    pub fn year(self) -> Year {
+        self.year
+    }
+
+    pub fn day(self) -> Day {
+        self.day
+    }
+
+    /// Returns the path of the data file for this puzzle in `folder`,
+    /// e.g. `data/2024/inputs/01.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> String {
+        format!("data/{}/{folder}/{}.{extension}", self.year, self.day)
+    }
+
+    /// Returns the path of the data file for one part of this puzzle, e.g. `.../examples/01-2.txt`.
+    pub fn data_part_path(self, folder: &str, part: u8, extension: &str) -> String {
+        format!(
+            "data/{}/{folder}/{}-{part}.{extension}",
+            self.year, self.day
+        )
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the current puzzle if today is one of the days of the running event,
+    /// `None` otherwise.
+    pub fn today() -> Option<Self> {
+        Self::new(Year::today()?, Day::today()?)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s.split_once('-').ok_or(PuzzleIdFromStrError)?;
+        let year = year.parse().map_err(|_| PuzzleIdFromStrError)?;
+        let day = day.parse().map_err(|_| PuzzleIdFromStrError)?;
+        Self::new(year, day).ok_or(PuzzleIdFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub struct PuzzleIdFromStrError;
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a puzzle in the format `{year}-{day}`, e.g. `2024-01`")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator that yields every puzzle of a year's event.
+pub fn all_puzzles(year: Year) -> impl Iterator<Item = PuzzleId> {
+    all_days()
+        .take_while(move |day| *day <= year.last_day())
+        .map(move |day| PuzzleId { year, day })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`PuzzleId`] value in a const context.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:expr, $day:expr) => {{
+        const _ASSERT: () = assert!(
+            $day <= $crate::year!($year).last_day(),
+            concat!("day `", $day, "` is not part of the ", $year, " event")
+        );
+        $crate::template::PuzzleId::__new_unchecked($crate::year!($year), $crate::day!($day))
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{all_puzzles, PuzzleId};
+
+    use crate::{puzzle, year};
+
+    #[test]
+    fn parses_puzzle_ids() {
+        assert_eq!("2024-08".parse::<PuzzleId>().unwrap(), puzzle!(2024, 8));
+        assert!("2024-26".parse::<PuzzleId>().is_err());
+        assert!("2025-13".parse::<PuzzleId>().is_err());
+        assert!("08".parse::<PuzzleId>().is_err());
+    }
+
+    #[test]
+    fn builds_data_paths() {
+        assert_eq!(
+            puzzle!(2023, 1).data_path("inputs", "txt"),
+            "data/2023/inputs/01.txt"
+        );
+        assert_eq!(
+            puzzle!(2023, 1).data_part_path("examples", 2, "txt"),
+            "data/2023/examples/01-2.txt"
+        );
+    }
+
+    #[test]
+    fn all_puzzles_iterator() {
+        assert_eq!(all_puzzles(year!(2024)).count(), 25);
+        assert_eq!(all_puzzles(year!(2025)).count(), 12);
+        assert_eq!(all_puzzles(year!(2025)).last(), Some(puzzle!(2025, 12)));
+    }
+}
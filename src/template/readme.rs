@@ -0,0 +1,495 @@
+/// Generates the sections of the readme. Every section lives between a pair of markers on lines of
+/// their own, e.g. `<!--- progress table --->`, and is replaced whenever the readme is updated.
+/// Sections whose markers are missing are skipped, so each readme can pick the sections it wants.
+use std::{fs, io};
+
+use crate::template::answers::Answers;
+use crate::template::commands::scaffold::read_title;
+use crate::template::memory::format_bytes;
+use crate::template::readme_benchmarks;
+use crate::template::registry::{self, Solution};
+use crate::template::stats::format_nanos;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{all_puzzles, PuzzleId, Year};
+
+static PROGRESS_MARKER: &str = "<!--- progress table --->";
+static TITLE_MARKER: &str = "<!--- title --->";
+
+#[allow(dead_code)]
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+pub struct TablePosition {
+    pos_start: usize,
+    pos_end: usize,
+}
+
+#[must_use]
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{puzzle}.rs")
+}
+
+/// Returns the byte ranges of the markers that are alone on their line. Mentions of a marker
+/// within a line, e.g. in the docs, are ignored.
+fn find_markers(readme: &str, marker: &str) -> Vec<(usize, usize)> {
+    let mut offset = 0;
+    readme
+        .split_inclusive('\n')
+        .filter_map(|line| {
+            let start = offset;
+            offset += line.len();
+            (line.trim_end() == marker).then_some((start, start + marker.len()))
+        })
+        .collect()
+}
+
+fn has_section(readme: &str, marker: &str) -> bool {
+    !find_markers(readme, marker).is_empty()
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    match find_markers(readme, marker)[..] {
+        [(pos_start, _), (_, pos_end)] => Ok(TablePosition { pos_start, pos_end }),
+        ref matches => Err(Error::Parser(format!(
+            "{marker}: expected a start and an end marker on lines of their own, found {}.",
+            matches.len()
+        ))),
+    }
+}
+
+/// Replaces the section between the markers with `table`, which includes the markers itself.
+pub fn replace_section(readme: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(readme, marker)?;
+    readme.replace_range(positions.pos_start..positions.pos_end, table);
+    Ok(())
+}
+
+/// The data the sections of the readme are generated from.
+pub struct ReadmeData<'a> {
+    pub solutions: &'a [Solution],
+    pub answers: &'a Answers,
+    pub timings: &'a Timings,
+    /// Returns the title of a puzzle, e.g. "Day 13: Claw Contraption".
+    pub title: fn(PuzzleId) -> Option<String>,
+}
+
+impl ReadmeData<'_> {
+    /// Every year with a solution, a known answer or a benchmark, in ascending order.
+    fn years(&self) -> Vec<Year> {
+        let mut years = registry::years(self.solutions);
+        years.extend(self.answers.data.iter().map(|a| a.puzzle.year()));
+        years.extend(self.timings.data.iter().map(|t| t.puzzle.year()));
+        years.sort_unstable();
+        years.dedup();
+        years
+    }
+}
+
+/// Formats the stars of a part, empty if the part is not solved.
+fn format_star(answer: Option<&String>) -> &'static str {
+    if answer.is_some() {
+        "⭐"
+    } else {
+        " "
+    }
+}
+
+/// Formats the highest peak heap usage of the parts of a day.
+fn format_peak_memory(timing: Option<&Timing>) -> String {
+    timing
+        .and_then(|t| t.memory.iter().flatten().map(|m| m.peak_bytes).max())
+        .map_or("`-`".into(), |peak| format!("`{}`", format_bytes(peak)))
+}
+
+fn construct_progress_table(data: &ReadmeData) -> String {
+    let mut lines: Vec<String> = vec![PROGRESS_MARKER.into()];
+
+    for year in data.years() {
+        // days that have a solution or a known answer.
+        let puzzles: Vec<PuzzleId> = all_puzzles(year)
+            .filter(|puzzle| {
+                registry::find(data.solutions, *puzzle).is_some()
+                    || data.answers.data.iter().any(|a| a.puzzle == *puzzle)
+            })
+            .collect();
+        if puzzles.is_empty() {
+            continue;
+        }
+
+        let answers: Vec<_> = data
+            .answers
+            .data
+            .iter()
+            .filter(|a| a.puzzle.year() == year)
+            .collect();
+        let stars: usize = answers
+            .iter()
+            .map(|a| usize::from(a.part_1.is_some()) + usize::from(a.part_2.is_some()))
+            .sum();
+        let timings: Vec<&Timing> = data
+            .timings
+            .data
+            .iter()
+            .filter(|t| t.puzzle.year() == year)
+            .collect();
+
+        lines.push(format!("## {year} Progress"));
+        lines.push(String::new());
+        let mut summary = format!("**{stars}** / {} ⭐", all_puzzles(year).count() * 2);
+        if !timings.is_empty() {
+            let total_nanos: f64 = timings.iter().map(|t| t.total_nanos).sum();
+            summary.push_str(&format!(
+                " · Total runtime: `{}`",
+                format_nanos(total_nanos)
+            ));
+        }
+        lines.push(summary);
+        lines.push(String::new());
+
+        // only show the memory and complexity columns if any day of the year has them.
+        let has_memory = timings.iter().any(|t| t.memory.iter().any(Option::is_some));
+        let has_complexity = puzzles.iter().any(|puzzle| {
+            registry::find(data.solutions, *puzzle).is_some_and(|s| s.complexity.is_some())
+        });
+
+        let mut header = String::from("| Day | Puzzle | Part 1 | Part 2 | Runtime |");
+        let mut alignment = String::from("| :---: | :--- | :---: | :---: | ---: |");
+        if has_memory {
+            header.push_str(" Memory |");
+            alignment.push_str(" ---: |");
+        }
+        if has_complexity {
+            header.push_str(" Complexity |");
+            alignment.push_str(" :--- |");
+        }
+        lines.push(header);
+        lines.push(alignment);
+
+        for puzzle in puzzles {
+            let day = puzzle.day().into_inner();
+            let answer = answers.iter().find(|a| a.puzzle == puzzle);
+            let timing = timings.iter().find(|t| t.puzzle == puzzle).copied();
+            let solution = registry::find(data.solutions, puzzle);
+
+            // titles are read as "Day 13: Claw Contraption", the day is listed separately.
+            let title = (data.title)(puzzle)
+                .map(|title| match title.split_once(": ") {
+                    Some((_, name)) => name.to_string(),
+                    None => title,
+                })
+                .unwrap_or_else(|| format!("Day {day}"));
+
+            let day_cell = if solution.is_some() {
+                format!("[{day}]({})", get_path_for_bin(puzzle))
+            } else {
+                day.to_string()
+            };
+
+            let mut row =
+                format!(
+                "| {day_cell} | [{title}](https://adventofcode.com/{}/day/{day}) | {} | {} | {} |",
+                puzzle.year(),
+                format_star(answer.and_then(|a| a.part_1.as_ref())),
+                format_star(answer.and_then(|a| a.part_2.as_ref())),
+                timing.map_or("`-`".into(), |t| format!("`{}`", format_nanos(t.total_nanos))),
+            );
+            if has_memory {
+                row.push_str(&format!(" {} |", format_peak_memory(timing)));
+            }
+            if has_complexity {
+                let complexity = solution.and_then(|s| s.complexity).unwrap_or("");
+                row.push_str(&format!(" {complexity} |"));
+            }
+            lines.push(row);
+        }
+
+        lines.push(String::new());
+    }
+
+    lines.push(PROGRESS_MARKER.into());
+    lines.join("\n")
+}
+
+/// Formats the years for the title, e.g. `2024` or `2023, 2024`.
+fn format_years(years: &[Year]) -> String {
+    years
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The heading of the readme with the solved years, e.g. `# 🎄 Advent of Code 2023, 2024`.
+fn construct_title(years: &[Year]) -> String {
+    [
+        TITLE_MARKER.into(),
+        format!("# 🎄 Advent of Code {}", format_years(years)),
+        TITLE_MARKER.into(),
+    ]
+    .join("\n")
+}
+
+/// Updates every section of the readme whose markers are present. Returns the names of the
+/// updated sections.
+fn update_content(readme: &mut String, data: &ReadmeData) -> Result<Vec<&'static str>, Error> {
+    let mut updated = vec![];
+
+    if has_section(readme, PROGRESS_MARKER) {
+        replace_section(readme, PROGRESS_MARKER, &construct_progress_table(data))?;
+        updated.push("progress");
+    }
+
+    // the benchmarks stay untouched until timings are stored with `cargo time --store`.
+    if has_section(readme, readme_benchmarks::MARKER) && !data.timings.data.is_empty() {
+        readme_benchmarks::update_content(
+            readme,
+            data.timings.clone(),
+            data.timings.total_millis(),
+        )?;
+        updated.push("benchmarks");
+    }
+
+    // the title keeps its placeholder until a year is solved.
+    let years = data.years();
+    if has_section(readme, TITLE_MARKER) && !years.is_empty() {
+        replace_section(readme, TITLE_MARKER, &construct_title(&years))?;
+        updated.push("title");
+    }
+
+    Ok(updated)
+}
+
+/// Regenerates the readme from the registered solutions, `data/answers.json`, the stored
+/// timings and the downloaded puzzle descriptions.
+pub fn update(solutions: &[Solution]) -> Result<Vec<&'static str>, Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+
+    let data = ReadmeData {
+        solutions,
+        answers: &Answers::read_from_file(),
+        timings: &Timings::read_from_file(),
+        title: read_title,
+    };
+    let updated = update_content(&mut readme, &data)?;
+
+    fs::write(path, &readme)?;
+    Ok(updated)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{
+        puzzle,
+        template::{
+            answers::{Answers, KnownAnswers},
+            memory::Memory,
+            registry::{Solution, SolutionResult},
+            stats::Stats,
+            timings::{Timing, Timings},
+            PuzzleId,
+        },
+        year,
+    };
+
+    use super::{construct_title, update_content, ReadmeData, PROGRESS_MARKER, TITLE_MARKER};
+    use crate::template::readme_benchmarks::MARKER;
+
+    /// Returns the lines of the readme that are not part of a section.
+    fn outside_sections(readme: &str) -> Vec<&str> {
+        let mut is_inside = false;
+        readme
+            .lines()
+            .filter(|line| {
+                let is_marker = [PROGRESS_MARKER, MARKER, TITLE_MARKER].contains(line);
+                is_inside ^= is_marker;
+                !is_inside && !is_marker
+            })
+            .collect()
+    }
+
+    fn solution(puzzle: PuzzleId, complexity: Option<&'static str>) -> Solution {
+        Solution {
+            puzzle,
+            runner: |_, _| SolutionResult {
+                parse: None,
+                parts: vec![],
+            },
+            complexity,
+        }
+    }
+
+    fn title(puzzle: PuzzleId) -> Option<String> {
+        (puzzle == puzzle!(2024, 1)).then(|| "Day 1: Historian Hysteria".to_string())
+    }
+
+    fn get_mock_data() -> (Vec<Solution>, Answers, Timings) {
+        let solutions = vec![
+            solution(puzzle!(2024, 1), Some("O(n log n)")),
+            solution(puzzle!(2024, 2), None),
+        ];
+        let answers = Answers {
+            data: vec![
+                KnownAnswers {
+                    puzzle: puzzle!(2024, 1),
+                    part_1: Some("11".into()),
+                    part_2: Some("31".into()),
+                },
+                KnownAnswers {
+                    puzzle: puzzle!(2024, 2),
+                    part_1: Some("2".into()),
+                    part_2: None,
+                },
+            ],
+        };
+        let timings = Timings {
+            data: vec![Timing {
+                puzzle: puzzle!(2024, 1),
+                parse: None,
+                part_1: Some(Stats::from_single(1_000_000.0)),
+                part_2: Some(Stats::from_single(2_000_000.0)),
+                total_nanos: 3_000_000.0,
+                failed: vec![],
+                parse_memory: None,
+                memory: [
+                    Some(Memory {
+                        total_bytes: 4096,
+                        peak_bytes: 2048,
+                        allocations: 3,
+                    }),
+                    None,
+                ],
+            }],
+        };
+        (solutions, answers, timings)
+    }
+
+    #[test]
+    fn generates_progress_table() {
+        let (solutions, answers, timings) = get_mock_data();
+        let data = ReadmeData {
+            solutions: &solutions,
+            answers: &answers,
+            timings: &timings,
+            title,
+        };
+
+        let mut s = format!(
+            "{TITLE_MARKER}\n# 🎄 Advent of Code {{year}}\n{TITLE_MARKER}\n\n\
+             {PROGRESS_MARKER}\n{PROGRESS_MARKER}\nfoo"
+        );
+        let updated = update_content(&mut s, &data).unwrap();
+        assert_eq!(updated, vec!["progress", "title"]);
+
+        let expected = [
+            "<!--- title --->",
+            "# 🎄 Advent of Code 2024",
+            "<!--- title --->",
+            "",
+            "<!--- progress table --->",
+            "## 2024 Progress",
+            "",
+            "**3** / 50 ⭐ · Total runtime: `3.0ms`",
+            "",
+            "| Day | Puzzle | Part 1 | Part 2 | Runtime | Memory | Complexity |",
+            "| :---: | :--- | :---: | :---: | ---: | ---: | :--- |",
+            "| [1](./src/bin/2024-01.rs) | [Historian Hysteria](https://adventofcode.com/2024/day/1) | ⭐ | ⭐ | `3.0ms` | `2.0 KiB` | O(n log n) |",
+            "| [2](./src/bin/2024-02.rs) | [Day 2](https://adventofcode.com/2024/day/2) | ⭐ |   | `-` | `-` |  |",
+            "",
+            "<!--- progress table --->",
+            "foo",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+
+        // markers are kept, so the section can be regenerated.
+        update_content(&mut s, &data).unwrap();
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn skips_missing_sections() {
+        let (solutions, answers, timings) = get_mock_data();
+        let data = ReadmeData {
+            solutions: &solutions,
+            answers: &answers,
+            timings: &timings,
+            title,
+        };
+
+        let mut s = "# readme\n\nsee `data/{year}/inputs`".to_string();
+        assert!(update_content(&mut s, &data).unwrap().is_empty());
+        assert_eq!(s, "# readme\n\nsee `data/{year}/inputs`");
+
+        // benchmarks are only generated once timings are stored.
+        let timings = Timings::default();
+        let data = ReadmeData {
+            timings: &timings,
+            ..data
+        };
+        let mut s = format!("{MARKER}\n{MARKER}");
+        assert!(update_content(&mut s, &data).unwrap().is_empty());
+        assert_eq!(s, format!("{MARKER}\n{MARKER}"));
+    }
+
+    #[test]
+    fn reads_markers_on_lines_of_their_own() {
+        let (solutions, answers, timings) = get_mock_data();
+        let data = ReadmeData {
+            solutions: &solutions,
+            answers: &answers,
+            timings: &timings,
+            title,
+        };
+
+        let mut s =
+            format!("{TITLE_MARKER}\r\n# {{year}}\r\n{TITLE_MARKER}\r\nsee `{TITLE_MARKER}`");
+        assert_eq!(update_content(&mut s, &data).unwrap(), vec!["title"]);
+        assert_eq!(
+            s,
+            format!("{}\r\nsee `{TITLE_MARKER}`", construct_title(&data.years()))
+        );
+
+        let mut s = format!("{TITLE_MARKER}\n# {{year}}\n");
+        assert!(update_content(&mut s, &data).is_err());
+    }
+
+    #[test]
+    fn keeps_the_readme_outside_of_sections() {
+        let (solutions, answers, timings) = get_mock_data();
+        let data = ReadmeData {
+            solutions: &solutions,
+            answers: &answers,
+            timings: &timings,
+            title,
+        };
+
+        let readme = include_str!("../../README.md");
+        let mut s = readme.to_string();
+        assert_eq!(
+            update_content(&mut s, &data).unwrap(),
+            vec!["benchmarks", "title"]
+        );
+        assert!(s.contains("## 2024 Benchmarks"));
+        assert_eq!(outside_sections(&s), outside_sections(readme));
+    }
+
+    #[test]
+    fn generates_title() {
+        assert_eq!(
+            construct_title(&[year!(2023), year!(2024)]),
+            format!("{TITLE_MARKER}\n# 🎄 Advent of Code 2023, 2024\n{TITLE_MARKER}")
+        );
+    }
+}
@@ -1,127 +1,243 @@
-/// Module that updates the readme me with timing information.
+/// Builds the benchmark section of the readme, see `readme` for the other sections.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use crate::template::memory::format_bytes;
+use crate::template::readme::{get_path_for_bin, replace_section, Error};
+use crate::template::stats::{format_nanos, Stats};
+use crate::template::timings::{Timing, Timings};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+pub static MARKER: &str = "<!--- benchmarking table --->";
 
-static MARKER: &str = "<!--- benchmarking table --->";
+/// Formats the median of a part, with its standard deviation if it was sampled more than once.
+fn format_part(timing: &Timing, part: u8) -> String {
+    if timing.failed.contains(&part) {
+        return "`failed`".into();
+    }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
+    format_stats(match part {
+        1 => timing.part_1.as_ref(),
+        _ => timing.part_2.as_ref(),
+    })
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+fn format_stats(stats: Option<&Stats>) -> String {
+    match stats {
+        Some(stats) if stats.samples > 1 => format!(
+            "`{}` ± {}",
+            format_nanos(stats.median),
+            format_nanos(stats.std_dev)
+        ),
+        Some(stats) => format!("`{}`", format_nanos(stats.median)),
+        None => "`-`".into(),
     }
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+/// Formats the peak heap usage of a part.
+fn format_memory(timing: &Timing, part: u8) -> String {
+    match timing.memory[usize::from(part) - 1] {
+        Some(memory) => format!("`{}`", format_bytes(memory.peak_bytes)),
+        None => "`-`".into(),
+    }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
+fn construct_memory_table(lines: &mut Vec<String>, timings: &[&Timing]) {
+    lines.push("<details>".into());
+    lines.push("<summary>Memory</summary>".into());
+    lines.push(String::new());
+    lines.push("| Day | Part | Peak | Total | Allocations |".into());
+    lines.push("| :---: | :---: | ---: | ---: | ---: |".into());
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+    for timing in timings {
+        let phases = [("Parse", timing.parse_memory)]
+            .into_iter()
+            .chain(["1", "2"].into_iter().zip(timing.memory));
 
-    if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        for (part, memory) in phases {
+            let Some(memory) = memory else {
+                continue;
+            };
+            lines.push(format!(
+                "| {} | {part} | `{}` | `{}` | {} |",
+                timing.puzzle.day().into_inner(),
+                format_bytes(memory.peak_bytes),
+                format_bytes(memory.total_bytes),
+                memory.allocations,
+            ));
+        }
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    lines.push(String::new());
+    lines.push("</details>".into());
+    lines.push(String::new());
+}
+
+fn construct_stats_table(lines: &mut Vec<String>, timings: &[&Timing]) {
+    lines.push("<details>".into());
+    lines.push("<summary>Statistics</summary>".into());
+    lines.push(String::new());
+    lines.push(
+        "| Day | Part | Median | Mean | Min | Max | Std. dev. | p90 | p99 | Samples | Outliers |"
+            .into(),
+    );
+    lines.push(
+        "| :---: | :---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: |".into(),
+    );
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+    for timing in timings {
+        for (part, stats) in [
+            ("parse", &timing.parse),
+            ("1", &timing.part_1),
+            ("2", &timing.part_2),
+        ] {
+            let Some(stats) = stats else {
+                continue;
+            };
+            lines.push(format!(
+                "| {} | {part} | `{}` | `{}` | `{}` | `{}` | `{}` | `{}` | `{}` | {} | {} |",
+                timing.puzzle.day().into_inner(),
+                format_nanos(stats.median),
+                format_nanos(stats.mean),
+                format_nanos(stats.min),
+                format_nanos(stats.max),
+                format_nanos(stats.std_dev),
+                format_nanos(stats.p90),
+                format_nanos(stats.p99),
+                stats.samples,
+                stats.outliers,
+            ));
+        }
+    }
 
-    Ok(TablePosition { pos_start, pos_end })
+    lines.push(String::new());
+    lines.push("</details>".into());
+    lines.push(String::new());
 }
 
 fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+    let mut lines: Vec<String> = vec![MARKER.into()];
+
+    let mut years: Vec<_> = timings.data.iter().map(|t| t.puzzle.year()).collect();
+    years.dedup();
+
+    for year in years {
+        let year_timings: Vec<&Timing> = timings
+            .data
+            .iter()
+            .filter(|t| t.puzzle.year() == year)
+            .collect();
+
+        lines.push(format!("{prefix} {year} Benchmarks"));
+        lines.push(String::new());
+        // only show the parse column if a solution of the year shares its parsed input.
+        let has_parse = year_timings.iter().any(|t| t.parse.is_some());
+        // only show the memory columns if a solution of the year was benched with `--memory`.
+        let has_memory = year_timings
+            .iter()
+            .any(|t| t.parse_memory.is_some() || t.memory.iter().any(Option::is_some));
+
+        let mut header = String::from("| Day |");
+        let mut alignment = String::from("| :---: |");
+        if has_parse {
+            header.push_str(" Parse |");
+            alignment.push_str(" :---: |");
+        }
+        header.push_str(" Part 1 | Part 2 |");
+        alignment.push_str(" :---: | :---:  |");
+        if has_memory {
+            header.push_str(" Memory 1 | Memory 2 |");
+            alignment.push_str(" :---: | :---: |");
+        }
+        lines.push(header);
+        lines.push(alignment);
+
+        for timing in &year_timings {
+            let path = get_path_for_bin(timing.puzzle);
+            let parse = if has_parse {
+                format!(" {} |", format_stats(timing.parse.as_ref()))
+            } else {
+                String::new()
+            };
+            let memory = if has_memory {
+                format!(
+                    " {} | {} |",
+                    format_memory(timing, 1),
+                    format_memory(timing, 2)
+                )
+            } else {
+                String::new()
+            };
+            lines.push(format!(
+                "| [Day {}]({}) |{parse} {} | {} |{memory}",
+                timing.puzzle.day().into_inner(),
+                path,
+                format_part(timing, 1),
+                format_part(timing, 2)
+            ));
+        }
+
+        lines.push(String::new());
+        construct_stats_table(&mut lines, &year_timings);
+        if has_memory {
+            construct_memory_table(&mut lines, &year_timings);
+        }
     }
 
-    lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
+pub fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
     let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
-}
-
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
-    Ok(())
+    replace_section(s, MARKER, &table)
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{format_part, update_content, MARKER};
+    use crate::{
+        puzzle,
+        template::memory::Memory,
+        template::stats::Stats,
+        template::timings::{Timing, Timings},
+    };
+
+    fn ms(millis: u32) -> Stats {
+        Stats::from_single(f64::from(millis) * 1_000_000.0)
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    puzzle: puzzle!(2024, 1),
+                    parse: None,
+                    part_1: Some(ms(10)),
+                    part_2: Some(ms(20)),
                     total_nanos: 3e+10,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    puzzle: puzzle!(2024, 2),
+                    parse: None,
+                    part_1: Some(ms(30)),
+                    part_2: Some(ms(40)),
                     total_nanos: 7e+10,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    puzzle: puzzle!(2024, 4),
+                    parse: None,
+                    part_1: Some(ms(40)),
+                    part_2: Some(ms(50)),
                     total_nanos: 9e+10,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 },
             ],
         }
@@ -143,18 +259,21 @@ mod tests {
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
         update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        assert_eq!(s.contains("## 2024 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
         update_content(&mut s, get_mock_timings(), 190.0).unwrap();
         update_content(&mut s, get_mock_timings(), 190.0).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## 2024 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
@@ -165,13 +284,27 @@ mod tests {
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## 2024 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `50.0ms` |",
+            "",
+            "<details>",
+            "<summary>Statistics</summary>",
+            "",
+            "| Day | Part | Median | Mean | Min | Max | Std. dev. | p90 | p99 | Samples | Outliers |",
+            "| :---: | :---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: |",
+            "| 1 | 1 | `10.0ms` | `10.0ms` | `10.0ms` | `10.0ms` | `0.0ns` | `10.0ms` | `10.0ms` | 1 | 0 |",
+            "| 1 | 2 | `20.0ms` | `20.0ms` | `20.0ms` | `20.0ms` | `0.0ns` | `20.0ms` | `20.0ms` | 1 | 0 |",
+            "| 2 | 1 | `30.0ms` | `30.0ms` | `30.0ms` | `30.0ms` | `0.0ns` | `30.0ms` | `30.0ms` | 1 | 0 |",
+            "| 2 | 2 | `40.0ms` | `40.0ms` | `40.0ms` | `40.0ms` | `0.0ns` | `40.0ms` | `40.0ms` | 1 | 0 |",
+            "| 4 | 1 | `40.0ms` | `40.0ms` | `40.0ms` | `40.0ms` | `0.0ns` | `40.0ms` | `40.0ms` | 1 | 0 |",
+            "| 4 | 2 | `50.0ms` | `50.0ms` | `50.0ms` | `50.0ms` | `0.0ns` | `50.0ms` | `50.0ms` | 1 | 0 |",
+            "",
+            "</details>",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +313,195 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_for_multiple_years() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    puzzle: puzzle!(2023, 1),
+                    parse: None,
+                    part_1: Some(ms(10)),
+                    part_2: None,
+                    total_nanos: 1e+10,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
+                },
+                Timing {
+                    puzzle: puzzle!(2024, 1),
+                    parse: None,
+                    part_1: Some(ms(30)),
+                    part_2: Some(ms(40)),
+                    total_nanos: 7e+10,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
+                },
+            ],
+        };
+        update_content(&mut s, timings, 80.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## 2023 Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10.0ms` | `-` |",
+            "",
+            "<details>",
+            "<summary>Statistics</summary>",
+            "",
+            "| Day | Part | Median | Mean | Min | Max | Std. dev. | p90 | p99 | Samples | Outliers |",
+            "| :---: | :---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: |",
+            "| 1 | 1 | `10.0ms` | `10.0ms` | `10.0ms` | `10.0ms` | `0.0ns` | `10.0ms` | `10.0ms` | 1 | 0 |",
+            "",
+            "</details>",
+            "",
+            "## 2024 Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2024-01.rs) | `30.0ms` | `40.0ms` |",
+            "",
+            "<details>",
+            "<summary>Statistics</summary>",
+            "",
+            "| Day | Part | Median | Mean | Min | Max | Std. dev. | p90 | p99 | Samples | Outliers |",
+            "| :---: | :---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: |",
+            "| 1 | 1 | `30.0ms` | `30.0ms` | `30.0ms` | `30.0ms` | `0.0ns` | `30.0ms` | `30.0ms` | 1 | 0 |",
+            "| 1 | 2 | `40.0ms` | `40.0ms` | `40.0ms` | `40.0ms` | `0.0ns` | `40.0ms` | `40.0ms` | 1 | 0 |",
+            "",
+            "</details>",
+            "",
+            "**Total: 80.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_phase() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    puzzle: puzzle!(2024, 1),
+                    parse: Some(ms(5)),
+                    part_1: Some(ms(10)),
+                    part_2: Some(ms(20)),
+                    total_nanos: 3.5e+7,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
+                },
+                Timing {
+                    puzzle: puzzle!(2024, 2),
+                    parse: None,
+                    part_1: Some(ms(30)),
+                    part_2: None,
+                    total_nanos: 3e+7,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
+                },
+            ],
+        };
+        update_content(&mut s, timings, 65.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## 2024 Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2024-01.rs) | `5.0ms` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `-` | `30.0ms` | `-` |",
+            "",
+            "<details>",
+            "<summary>Statistics</summary>",
+            "",
+            "| Day | Part | Median | Mean | Min | Max | Std. dev. | p90 | p99 | Samples | Outliers |",
+            "| :---: | :---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: |",
+            "| 1 | parse | `5.0ms` | `5.0ms` | `5.0ms` | `5.0ms` | `0.0ns` | `5.0ms` | `5.0ms` | 1 | 0 |",
+            "| 1 | 1 | `10.0ms` | `10.0ms` | `10.0ms` | `10.0ms` | `0.0ns` | `10.0ms` | `10.0ms` | 1 | 0 |",
+            "| 1 | 2 | `20.0ms` | `20.0ms` | `20.0ms` | `20.0ms` | `0.0ns` | `20.0ms` | `20.0ms` | 1 | 0 |",
+            "| 2 | 1 | `30.0ms` | `30.0ms` | `30.0ms` | `30.0ms` | `0.0ns` | `30.0ms` | `30.0ms` | 1 | 0 |",
+            "",
+            "</details>",
+            "",
+            "**Total: 65.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        let timings = Timings {
+            data: vec![Timing {
+                puzzle: puzzle!(2024, 1),
+                parse: None,
+                part_1: Some(ms(10)),
+                part_2: Some(ms(20)),
+                total_nanos: 3e+7,
+                failed: vec![],
+                parse_memory: Some(Memory {
+                    total_bytes: 512,
+                    peak_bytes: 256,
+                    allocations: 2,
+                }),
+                memory: [
+                    Some(Memory {
+                        total_bytes: 4096,
+                        peak_bytes: 1536,
+                        allocations: 12,
+                    }),
+                    None,
+                ],
+            }],
+        };
+        update_content(&mut s, timings, 30.0).unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "| Day | Part 1 | Part 2 | Memory 1 | Memory 2 |");
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` | `1.5 KiB` | `-` |"
+        );
+        assert!(lines.contains(&"| 1 | Parse | `256 B` | `512 B` | 2 |"));
+        assert!(lines.contains(&"| 1 | 1 | `1.5 KiB` | `4.0 KiB` | 12 |"));
+    }
+
+    #[test]
+    fn formats_parts() {
+        let stats = Stats {
+            std_dev: 250_000.0,
+            samples: 100,
+            ..ms(10)
+        };
+        let timing = Timing {
+            puzzle: puzzle!(2024, 1),
+            parse: None,
+            part_1: Some(stats),
+            part_2: None,
+            total_nanos: 1e+7,
+            failed: vec![],
+            parse_memory: None,
+            memory: [None, None],
+        };
+        assert_eq!(format_part(&timing, 1), "`10.0ms` ± 250.0µs");
+        assert_eq!(format_part(&timing, 2), "`-`");
+
+        let timing = Timing {
+            part_1: Some(ms(10)),
+            failed: vec![2],
+            ..timing
+        };
+        assert_eq!(format_part(&timing, 1), "`10.0ms`");
+        assert_eq!(format_part(&timing, 2), "`failed`");
+    }
 }
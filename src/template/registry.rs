@@ -0,0 +1,44 @@
+/// Solutions registered by the `solution!` macro.
+/// The main binary includes every solution module, which allows running them in-process.
+use crate::template::runner::{ParseResult, PartResult, RunOptions};
+use crate::template::{PuzzleId, Year};
+
+/// Runs every part of a solution against an input.
+pub type SolutionRunner = fn(input: &str, options: &RunOptions) -> SolutionResult;
+
+/// A solution as registered by the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub runner: SolutionRunner,
+    /// A note on the complexity of the solution, listed in the progress table of the readme.
+    pub complexity: Option<&'static str>,
+}
+
+/// The outcome of running a solution.
+#[derive(Clone, Debug)]
+pub struct SolutionResult {
+    /// The parse phase, if the solution parses its input once for both parts.
+    pub parse: Option<ParseResult>,
+    pub parts: Vec<PartResult>,
+}
+
+impl Solution {
+    /// Run every registered part of the solution against the input.
+    pub fn run(&self, input: &str, options: &RunOptions) -> SolutionResult {
+        (self.runner)(input, options)
+    }
+}
+
+/// Look up the solution for a puzzle.
+pub fn find(solutions: &[Solution], puzzle: PuzzleId) -> Option<&Solution> {
+    solutions.iter().find(|s| s.puzzle == puzzle)
+}
+
+/// Returns every year that has at least one registered solution, in ascending order.
+pub fn years(solutions: &[Solution]) -> Vec<Year> {
+    let mut years: Vec<Year> = solutions.iter().map(|s| s.puzzle.year()).collect();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
@@ -1,257 +1,370 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-
-use super::{
-    all_days,
-    timings::{Timing, Timings},
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    sync::{mpsc, Mutex},
+    thread,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use crate::template::output;
+use crate::template::registry::{self, Solution, SolutionResult};
+use crate::template::runner::{self, ParseResult, PartResult, RunOptions};
+use crate::template::stats::Stats;
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
-    let mut need_space = false;
+use super::timings::{Timing, Timings};
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// The outcome of running a single puzzle.
+enum DayResult {
+    NotSolved,
+    MissingInput(String),
+    Solved(Box<SolutionResult>),
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+/// Runs the registered solutions for a set of puzzles in-process, in puzzle order. With more than
+/// one job, puzzles run concurrently on that many threads, and the output of every puzzle is
+/// printed as a block once it and all puzzles before it finished. A job count of `0` uses one job
+/// per available core. Returns the timings of the puzzles if they were timed or their heap usage
+/// was measured.
+pub fn run_multi(
+    solutions: &[Solution],
+    puzzles_to_run: &HashSet<PuzzleId>,
+    options: &RunOptions,
+    jobs: usize,
+) -> Option<Timings> {
+    let mut timings: Vec<Timing> = Vec::with_capacity(puzzles_to_run.len());
+    let mut all_results: Vec<PartResult> = vec![];
+    let is_text = options.format.is_text();
+
+    let mut puzzles: Vec<PuzzleId> = puzzles_to_run.iter().copied().collect();
+    puzzles.sort_unstable();
+
+    let jobs = match jobs {
+        0 => thread::available_parallelism().map_or(1, usize::from),
+        jobs => jobs,
+    }
+    .min(puzzles.len());
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut collect = |puzzle: PuzzleId, result: DayResult| match result {
+        DayResult::NotSolved => {}
+        DayResult::MissingInput(input_path) => {
+            eprintln!("Could not open input file \"{input_path}\".");
+        }
+        DayResult::Solved(result) => {
+            timings.push(to_timing(puzzle, result.parse.as_ref(), &result.parts));
+            all_results.extend(result.parts);
+        }
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    if jobs > 1 {
+        let quiet = RunOptions {
+            quiet: true,
+            ..*options
+        };
+
+        let mut need_space = false;
+        run_parallel(solutions, &puzzles, &quiet, jobs, |puzzle, result| {
+            if is_text {
+                print_header(puzzle, need_space);
+                need_space = true;
+                if matches!(result, DayResult::NotSolved) {
+                    println!("Not solved.");
+                }
             }
+            if let DayResult::Solved(result) = &result {
+                if let Some(parse) = &result.parse {
+                    runner::report_parse(parse, options.format);
+                }
+                for part in &result.parts {
+                    runner::report_part(part, options.format);
+                }
+            }
+            collect(puzzle, result);
         });
+    } else {
+        for (i, puzzle) in puzzles.into_iter().enumerate() {
+            if is_text {
+                print_header(puzzle, i > 0);
+            }
+            let result = run_day(solutions, puzzle, options);
+            if is_text && matches!(result, DayResult::NotSolved) {
+                println!("Not solved.");
+            }
+            collect(puzzle, result);
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    output::print_report(&all_results, options.format);
+
+    let timings = Timings { data: timings };
+    if options.is_timed && is_text {
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
     }
-}
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+    (options.is_timed || options.memory).then_some(timings)
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+fn print_header(puzzle: PuzzleId, need_space: bool) {
+    if need_space {
+        println!();
     }
+    println!(
+        "{ANSI_BOLD}{} Day {}{ANSI_RESET}",
+        puzzle.year(),
+        puzzle.day()
+    );
+    println!("-----------");
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+fn run_day(solutions: &[Solution], puzzle: PuzzleId, options: &RunOptions) -> DayResult {
+    let Some(solution) = registry::find(solutions, puzzle) else {
+        return DayResult::NotSolved;
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    let input_path = puzzle.data_path("inputs", "txt");
+    match fs::read_to_string(&input_path) {
+        Ok(input) => DayResult::Solved(Box::new(solution.run(&input, options))),
+        Err(_) => DayResult::MissingInput(input_path),
+    }
+}
 
-        if is_release {
-            args.push("--release");
+/// Runs puzzles on `jobs` threads. `on_result` is called with the result of every puzzle in the
+/// order of `puzzles`, as soon as all puzzles before it finished.
+fn run_parallel(
+    solutions: &[Solution],
+    puzzles: &[PuzzleId],
+    options: &RunOptions,
+    jobs: usize,
+    mut on_result: impl FnMut(PuzzleId, DayResult),
+) {
+    let queue = Mutex::new(puzzles.iter().copied().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let queue = &queue;
+            scope.spawn(move || loop {
+                let Some((index, puzzle)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let result = run_day(solutions, puzzle, options);
+                if sender.send((index, puzzle, result)).is_err() {
+                    break;
+                }
+            });
         }
-
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        drop(sender);
+
+        // finished puzzles wait here until every puzzle before them finished as well.
+        let mut pending: HashMap<usize, (PuzzleId, DayResult)> = HashMap::new();
+        let mut next = 0;
+        for (index, puzzle, result) in receiver {
+            pending.insert(index, (puzzle, result));
+            while let Some((puzzle, result)) = pending.remove(&next) {
+                on_result(puzzle, result);
+                next += 1;
+            }
         }
+    });
+}
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+/// Collects the results of a solution's parse phase and parts into a [`Timing`].
+/// Parts that did not produce an answer are not timed, parts that returned an error are marked
+/// as failed. A parse phase that returned an error is not timed either.
+fn to_timing(puzzle: PuzzleId, parse: Option<&ParseResult>, results: &[PartResult]) -> Timing {
+    #[allow(clippy::cast_precision_loss)]
+    let parse = parse.filter(|parse| parse.error.is_none());
+    let parse_memory = parse.and_then(|parse| parse.memory);
+    let parse = parse.map(|parse| {
+        parse
+            .stats
+            .unwrap_or_else(|| Stats::from_single(parse.duration.as_nanos() as f64))
+    });
+
+    let mut timing = Timing {
+        puzzle,
+        parse,
+        part_1: None,
+        part_2: None,
+        total_nanos: parse.map_or(0_f64, |stats| stats.median),
+        failed: results
+            .iter()
+            .filter(|r| r.error.is_some())
+            .map(|r| r.part)
+            .collect(),
+        parse_memory,
+        memory: [None, None],
+    };
 
-        let mut output = vec![];
+    for result in results.iter().filter(|r| r.answer.is_some()) {
+        #[allow(clippy::cast_precision_loss)]
+        let stats = result
+            .stats
+            .unwrap_or_else(|| Stats::from_single(result.duration.as_nanos() as f64));
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
-
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        match result.part {
+            1 => timing.part_1 = Some(stats),
+            2 => timing.part_2 = Some(stats),
+            _ => continue,
         }
+        timing.memory[usize::from(result.part) - 1] = result.memory;
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+        timing.total_nanos += stats.median;
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    timing
+}
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{run_parallel, to_timing};
+    use crate::{
+        puzzle,
+        template::{
+            runner::{self, ParseResult, PartResult, RunOptions},
+            stats::Stats,
+            Answer, Day, PuzzleId,
+        },
+        year,
+    };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    fn part_result(part: u8, answer: Option<&str>, nanos: u64) -> PartResult {
+        PartResult {
+            puzzle: puzzle!(2024, 1),
+            part,
+            answer: answer.map(|answer| Answer::Text(answer.into())),
+            error: None,
+            duration: Duration::from_nanos(nanos),
+            samples: 1,
+            stats: None,
+            memory: None,
+        }
+    }
 
-                timings.total_nanos += nanos;
-            });
+    #[test]
+    fn collects_part_timings() {
+        let timing = to_timing(
+            puzzle!(2024, 1),
+            None,
+            &[
+                part_result(1, Some("0"), 74_130),
+                part_result(2, Some("10"), 74_130_000),
+            ],
+        );
+        assert_eq!(timing.total_nanos, 74_204_130_f64);
+        assert_eq!(timing.part_1.unwrap().median, 74_130_f64);
+        assert_eq!(timing.part_2.unwrap().median, 74_130_000_f64);
+    }
 
-        timings
+    #[test]
+    fn uses_benchmark_statistics() {
+        let stats = Stats::from_durations(&[
+            Duration::from_nanos(10),
+            Duration::from_nanos(20),
+            Duration::from_nanos(30),
+        ])
+        .unwrap();
+
+        let mut result = part_result(1, Some("0"), 20);
+        result.stats = Some(stats);
+
+        let timing = to_timing(puzzle!(2024, 1), None, &[result]);
+        assert_eq!(timing.part_1, Some(stats));
+        assert_eq!(timing.total_nanos, 20_f64);
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    #[test]
+    fn skips_missing_parts() {
+        let timing = to_timing(
+            puzzle!(2024, 1),
+            None,
+            &[part_result(1, None, 100), part_result(2, None, 100)],
+        );
+        assert_eq!(timing.total_nanos, 0_f64);
+        assert_eq!(timing.part_1.is_none(), true);
+        assert_eq!(timing.part_2.is_none(), true);
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn marks_failed_parts() {
+        let mut failed = part_result(2, None, 100);
+        failed.error = Some(vec!["invalid input".into()]);
+
+        let timing = to_timing(
+            puzzle!(2024, 1),
+            None,
+            &[part_result(1, Some("1"), 100), failed],
+        );
+        assert_eq!(timing.failed, vec![2]);
+        assert_eq!(timing.part_1.is_some(), true);
+        assert_eq!(timing.part_2.is_none(), true);
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn collects_parse_phase() {
+        let parse = ParseResult {
+            puzzle: puzzle!(2024, 1),
+            error: None,
+            duration: Duration::from_nanos(1_000),
+            samples: 1,
+            stats: None,
+            memory: None,
+        };
+
+        let timing = to_timing(
+            puzzle!(2024, 1),
+            Some(&parse),
+            &[
+                part_result(1, Some("0"), 2_000),
+                part_result(2, Some("10"), 3_000),
+            ],
+        );
+        assert_eq!(timing.parse.unwrap().median, 1_000_f64);
+        assert_eq!(timing.total_nanos, 6_000_f64);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn skips_failed_parse_phase() {
+        let parse = ParseResult {
+            puzzle: puzzle!(2024, 1),
+            error: Some(vec!["invalid digit".into()]),
+            duration: Duration::from_nanos(1_000),
+            samples: 1,
+            stats: None,
+            memory: None,
+        };
+        let options = RunOptions {
+            quiet: true,
+            ..RunOptions::default()
+        };
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+        let timing = to_timing(
+            puzzle!(2024, 1),
+            Some(&parse),
+            &[
+                runner::skip_part(puzzle!(2024, 1), 1, &options),
+                runner::skip_part(puzzle!(2024, 1), 2, &options),
+            ],
+        );
+        assert_eq!(timing.parse, None);
+        assert_eq!(timing.failed, vec![1, 2]);
+        assert_eq!(timing.total_nanos, 0_f64);
+    }
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn reports_parallel_results_in_order() {
+        let puzzles: Vec<PuzzleId> = (1..=9)
+            .map(|day| PuzzleId::new(year!(2024), Day::new(day).unwrap()).unwrap())
+            .collect();
+        let mut reported = vec![];
+        run_parallel(&[], &puzzles, &RunOptions::default(), 4, |puzzle, _| {
+            reported.push(puzzle);
+        });
+        assert_eq!(reported, puzzles);
     }
 }
@@ -2,64 +2,425 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process, slice};
 
+#[cfg(not(feature = "native-client"))]
+use crate::template::aoc_cli;
+#[cfg(feature = "native-client")]
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::bench::{BenchPolicy, BenchSettings};
+use crate::template::commands::examples;
+use crate::template::memory::{self, format_bytes, Memory};
+use crate::template::output::{self, OutputFormat};
+use crate::template::registry::Solution;
+use crate::template::stats::{format_nanos, Stats};
+use crate::template::submissions::{SubmissionLog, SubmissionVerdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{answers, Answer, ErrorChain, PartOutput, PuzzleId, ANSI_ITALIC, ANSI_RESET};
+use crate::visualize;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Options that control how solution parts are run and reported.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part instead of running it once.
+    pub is_timed: bool,
+    pub format: OutputFormat,
+    /// Do not report results, e.g. because the caller reports them itself.
+    pub quiet: bool,
+    /// Only run this part of a solution, run every part if `None`.
+    pub part: Option<u8>,
+    /// Measure the heap usage of every phase without writing a heap profile for each of them,
+    /// requires the `dhat-heap` feature.
+    pub memory: bool,
+    /// Bench settings passed on the command line, see `BenchSettings::resolve`.
+    pub bench: BenchSettings,
+}
+
+impl RunOptions {
+    /// Read the options passed to a solution binary.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let format = match args.iter().position(|x| x == "--format") {
+            Some(index) => match args.get(index + 1).map(|x| x.parse()) {
+                Some(Ok(format)) => format,
+                _ => {
+                    eprintln!("Unexpected command-line input. Format: cargo solve 1 --format json");
+                    process::exit(1);
+                }
+            },
+            None => OutputFormat::default(),
+        };
+
+        let number = |flag: &str| -> Option<u64> {
+            let index = args.iter().position(|x| x == flag)?;
+            match args.get(index + 1).map(|x| x.parse()) {
+                Some(Ok(value)) => Some(value),
+                _ => {
+                    eprintln!("Unexpected command-line input. Format: cargo solve 1 {flag} 500");
+                    process::exit(1);
+                }
+            }
+        };
+
+        let bench = BenchSettings {
+            warmup: number("--warmup-ms").map(Duration::from_millis),
+            budget: number("--budget-ms").map(Duration::from_millis),
+            min_samples: number("--min-samples").map(u128::from),
+            max_samples: number("--max-samples").map(u128::from),
+        };
+
+        Self {
+            is_timed: args.iter().any(|x| x == "--time"),
+            format,
+            quiet: false,
+            part: None,
+            memory: false,
+            bench,
+        }
+    }
+
+    /// Falls back to the bench settings of a solution for settings that were not passed on the
+    /// command line.
+    #[must_use]
+    pub fn with_bench(self, settings: BenchSettings) -> Self {
+        Self {
+            bench: self.bench.or(settings),
+            ..self
+        }
+    }
+
+    /// Whether a part of a solution should be run.
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    /// The error chain if the part returned an error.
+    pub error: Option<ErrorChain>,
+    /// The duration of a single run, or the median duration if the part was benched.
+    pub duration: Duration,
+    /// The number of runs, `0` if the part was skipped because its input could not be parsed.
+    pub samples: u128,
+    /// Statistics of the samples if the part was benched.
+    pub stats: Option<Stats>,
+    /// The heap usage of a single run, if it was measured.
+    pub memory: Option<Memory>,
+}
+
+impl PartResult {
+    /// Describes the outcome of the part, e.g. for machine-readable output.
+    pub fn status(&self) -> &'static str {
+        if self.error.is_some() {
+            "failed"
+        } else if self.answer.is_some() {
+            "solved"
+        } else {
+            "unsolved"
+        }
+    }
+}
+
+/// The return value of a parse function, either the parsed input or `Result<T, E>`.
+pub trait ParseOutput<T> {
+    /// The parsed input, or the error that stopped parsing.
+    fn into_parsed(self) -> Result<T, ErrorChain>;
+}
+
+impl<T> ParseOutput<T> for T {
+    fn into_parsed(self) -> Result<T, ErrorChain> {
+        Ok(self)
+    }
+}
+
+impl<T, E: Display> ParseOutput<T> for Result<T, E> {
+    fn into_parsed(self) -> Result<T, ErrorChain> {
+        self.map_err(|err| vec![err.to_string()])
+    }
+}
+
+/// The outcome of parsing the input of a solution that shares it between its parts.
+#[derive(Clone, Debug)]
+pub struct ParseResult {
+    pub puzzle: PuzzleId,
+    /// The error chain if the parse function returned an error.
+    pub error: Option<ErrorChain>,
+    /// The duration of a single run, or the median duration if parsing was benched.
+    pub duration: Duration,
+    pub samples: u128,
+    /// Statistics of the samples if parsing was benched.
+    pub stats: Option<Stats>,
+    /// The heap usage of a single run, if it was measured.
+    pub memory: Option<Memory>,
+}
+
+/// Parse the input of a solution once, report how long it took and return the parsed input.
+/// Returns `None` if the parse function returned an error, the parts should be skipped then.
+pub fn execute_parse<T, R: ParseOutput<T>>(
+    func: impl Fn(&str) -> R,
+    input: &str,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> (Option<T>, ParseResult) {
+    let is_text = options.format.is_text() && !options.quiet;
+
+    if is_text {
+        print!("Parse:");
+        let _ = stdout().flush();
+    }
+
+    let timer = Instant::now();
+    let (parsed, memory) =
+        memory::measure(|| func(input), profile_file(options, "parse").as_deref());
+    let parsed = parsed.into_parsed();
+    let base_time = timer.elapsed();
+
+    let (duration, stats) = if options.is_timed && parsed.is_ok() {
+        let stats = bench(&func, input, &options.bench.resolve(), is_text);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let median = Duration::from_nanos(stats.median.round() as u64);
+        (median, Some(stats))
+    } else {
+        (base_time, None)
+    };
+
+    let (parsed, error) = match parsed {
+        Ok(parsed) => (Some(parsed), None),
+        Err(chain) => (None, Some(chain)),
+    };
+
+    let result = ParseResult {
+        puzzle,
+        error,
+        duration,
+        samples: stats.map_or(1, |stats| stats.samples + stats.outliers),
+        stats,
+        memory,
+    };
+
+    if is_text {
+        print!("\r");
+        report_parse(&result, options.format);
+    }
+
+    (parsed, result)
+}
+
+/// Report how long parsing took, e.g. after parsing quietly.
+pub fn report_parse(result: &ParseResult, format: OutputFormat) {
+    if format.is_text() {
+        let duration_str = format!(
+            "{}{}",
+            format_duration(&result.duration, result.stats.as_ref()),
+            result
+                .memory
+                .as_ref()
+                .map(format_memory)
+                .unwrap_or_default()
+        );
+        match &result.error {
+            Some(chain) => print_result(&Err(chain.clone()), "Parse", &duration_str),
+            None => println!("Parse:{duration_str}"),
+        }
+    }
+}
+
+/// The result of a part that is not run because the input of the solution could not be parsed.
+/// The parse error is reported by the parse phase, the part only reports that it was skipped.
+pub fn skip_part(puzzle: PuzzleId, part: u8, options: &RunOptions) -> PartResult {
+    let result = PartResult {
+        puzzle,
+        part,
+        answer: None,
+        error: Some(vec!["the input could not be parsed".into()]),
+        duration: Duration::ZERO,
+        samples: 0,
+        stats: None,
+        memory: None,
+    };
+
+    if !options.quiet {
+        report_part(&result, options.format);
+    }
+    result
+}
+
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult {
+    let result = execute_part(func, input, puzzle, part, options);
+    visualize::finish(puzzle, part);
+
+    if let Some(answer) = &result.answer {
+        if submit_result(answer, puzzle, part) == Some(true) {
+            match answers::record(puzzle, part, &answer.value()) {
+                Ok(()) => println!("Stored answer for verification."),
+                Err(e) => eprintln!("Failed to store answer: {e}"),
+            }
+        }
+    }
+
+    result
+}
+
+/// Check the examples of a solution instead of solving the input if it was run with `--examples`,
+/// e.g. by `cargo solve 1 --watch`. Returns whether the examples were checked.
+pub fn check_examples_from_args(solution: &Solution, options: &RunOptions) -> bool {
+    if !env::args().any(|x| x == "--examples") {
+        return false;
+    }
+
+    examples::handle(
+        slice::from_ref(solution),
+        None,
+        Some(solution.puzzle),
+        options.format,
+    );
+    true
+}
+
+/// Run a solution part, report its result and return it.
+pub fn execute_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
+    let is_text = options.format.is_text() && !options.quiet;
+
+    let profile = profile_file(options, &part.to_string());
+    let (outcome, duration, stats, memory) = run_timed(func, input, options, profile, |outcome| {
+        if is_text {
+            print_result(outcome, &part_str, "");
+        }
+    });
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (answer, error) = match outcome {
+        Ok(answer) => (answer, None),
+        Err(chain) => (None, Some(chain)),
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let result = PartResult {
+        puzzle,
+        part,
+        answer,
+        error,
+        duration,
+        samples: stats.map_or(1, |stats| stats.samples + stats.outliers),
+        stats,
+        memory,
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if !options.quiet {
+        report_part(&result, options.format);
     }
+    result
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+/// Report the result of a part, e.g. after running it quietly.
+pub fn report_part(result: &PartResult, format: OutputFormat) {
+    if format.is_text() {
+        let outcome = match &result.error {
+            Some(chain) => Err(chain.clone()),
+            None => Ok(result.answer.clone()),
+        };
+        let duration_str = if result.samples == 0 {
+            " (skipped)".into()
+        } else {
+            format!(
+                "{}{}",
+                format_duration(&result.duration, result.stats.as_ref()),
+                result
+                    .memory
+                    .as_ref()
+                    .map(format_memory)
+                    .unwrap_or_default()
+            )
+        };
+        print_result(&outcome, &format!("Part {}", result.part), &duration_str);
+    }
+    output::print_part(result, format);
+}
+
+/// Run a solution part once, measuring its heap usage with the `dhat-heap` feature.
+/// If the run is timed and did not fail, the part is benched afterwards, see `BenchPolicy`.
+fn run_timed<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    options: &RunOptions,
+    profile: Option<String>,
+    hook: impl Fn(&Outcome),
+) -> (Outcome, Duration, Option<Stats>, Option<Memory>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
-
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
+        memory::measure(|| func(input), profile.as_deref())
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let outcome = result.to_outcome();
+    hook(&outcome);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    if options.is_timed && outcome.is_ok() {
+        let policy = options.bench.resolve();
+        let stats = bench(
+            func,
+            input,
+            &policy,
+            options.format.is_text() && !options.quiet,
+        );
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let median = Duration::from_nanos(stats.median.round() as u64);
+        (outcome, median, Some(stats), memory)
     } else {
-        (base_time, 1)
-    };
+        (outcome, base_time, None, memory)
+    }
+}
 
-    (result, run.0, run.1)
+/// The answer of a part, or the error chain if it failed.
+type Outcome = Result<Option<Answer>, ErrorChain>;
+
+/// The file the heap profile of a phase is written to with the `dhat-heap` feature, e.g.
+/// `dhat-heap-1.json` for `cargo solve --dhat`. `cargo time --memory` only needs the stats.
+fn profile_file(options: &RunOptions, phase: &str) -> Option<String> {
+    (!options.memory).then(|| format!("dhat-heap-{phase}.json"))
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    policy: &BenchPolicy,
+    show_progress: bool,
+) -> Stats {
+    if show_progress {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    // warm up caches and the branch predictor, then derive the sample count from the warm runs.
+    let warmup = Instant::now();
+    let mut warmup_iterations: u32 = 0;
+    while warmup_iterations == 0 || warmup.elapsed() < policy.warmup {
+        black_box(func(black_box(input.clone())));
+        warmup_iterations += 1;
+    }
+    let iteration_time = warmup.elapsed() / warmup_iterations;
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = policy.samples(iteration_time);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,45 +432,52 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: there are always at least `policy.min_samples` timers, which is at least one.
+    Stats::from_durations(&timers).unwrap()
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_duration(duration: &Duration, stats: Option<&Stats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            " ({} ± {} @ {} samples)",
+            format_nanos(stats.median),
+            format_nanos(stats.std_dev),
+            stats.samples + stats.outliers
+        ),
+        None => format!(" ({duration:.1?})"),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
-    }
+fn format_memory(memory: &Memory) -> String {
+    format!(
+        " [peak {}, total {} in {} allocations]",
+        format_bytes(memory.peak_bytes),
+        format_bytes(memory.total_bytes),
+        memory.allocations
+    )
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(outcome: &Outcome, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+    match outcome {
+        Ok(Some(answer)) => {
+            if answer.is_multiline() {
+                let str = match answer.submission() {
+                    Some(letters) => {
+                        format!("{part}: {ANSI_BOLD}{letters}{ANSI_RESET} ▼{duration_str}")
+                    }
+                    None => format!("{part}: ▼ {duration_str}"),
+                };
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
                     print!("\r");
                     println!("{str}");
-                    println!("{result}");
+                    println!("{answer}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -118,7 +486,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,17 +494,31 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(chain) => {
+            if is_intermediate_result {
+                print!("{part}: ✖");
+            } else {
+                print!("\r");
+                println!(
+                    "{part}: ✖ {ANSI_BOLD}error:{ANSI_RESET} {}{duration_str}",
+                    chain[0]
+                );
+                for cause in &chain[1..] {
+                    println!("  caused by: {cause}");
+                }
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. aoc-cli is installed, or the native client is enabled.
+///  3. the answer was not rejected before and lies within the bounds of earlier answers,
+///     unless `--force` is passed.
+///
+/// Returns whether the answer was accepted, or `None` if nothing was submitted.
+fn submit_result(answer: &Answer, puzzle: PuzzleId, part: u8) -> Option<bool> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +541,78 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    let Some(answer) = answer.submission() else {
+        eprintln!("Not submitting the answer: its letters could not be read, submit it manually.");
+        return Some(false);
+    };
+    let mut log = SubmissionLog::read_from_file(puzzle);
+
+    if !args.contains(&"--force".into()) {
+        if let Err(refusal) = log.check(part, &answer) {
+            eprintln!("Not submitting `{answer}`: {refusal} Pass `--force` to submit anyway.");
+            return Some(false);
+        }
+    }
+
+    let verdict = submit(puzzle, part, &answer)?;
+
+    log.record(part, &answer, verdict);
+    if let Err(e) = log.store_file() {
+        eprintln!("Failed to store submission: {e}");
+    }
+
+    Some(verdict == SubmissionVerdict::Correct)
+}
+
+/// Submit an answer, returns the verdict of the server if the answer was submitted.
+#[cfg(feature = "native-client")]
+fn submit(puzzle: PuzzleId, part: u8, answer: &str) -> Option<SubmissionVerdict> {
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to create client: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!("Submitting result...");
+    let error = match client.submit(puzzle, part, answer) {
+        Ok(()) => {
+            println!("That's the right answer!");
+            return Some(SubmissionVerdict::Correct);
+        }
+        Err(e) => e,
+    };
+
+    eprintln!("Submission failed: {error}");
+    match error {
+        AocClientError::WrongAnswer { hint } => match hint.as_deref() {
+            Some("too high") => Some(SubmissionVerdict::TooHigh),
+            Some("too low") => Some(SubmissionVerdict::TooLow),
+            _ => Some(SubmissionVerdict::Wrong),
+        },
+        AocClientError::TooSoon { .. } => Some(SubmissionVerdict::TooSoon),
+        AocClientError::AlreadySolved => Some(SubmissionVerdict::AlreadySolved),
+        _ => None,
+    }
+}
+
+/// Submit an answer, returns the verdict of the server if the answer was submitted.
+#[cfg(not(feature = "native-client"))]
+fn submit(puzzle: PuzzleId, part: u8, answer: &str) -> Option<SubmissionVerdict> {
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    match aoc_cli::submit(puzzle, part, answer) {
+        Ok(output) | Err(aoc_cli::AocCommandError::BadExitStatus(output)) => {
+            aoc_cli::submission_verdict(&output)
+        }
+        Err(e) => {
+            eprintln!("Submission failed: {e}");
+            None
+        }
+    }
 }
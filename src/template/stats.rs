@@ -0,0 +1,220 @@
+/// Summary statistics of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+
+use tinyjson::JsonValue;
+
+/// Samples outside of `TUKEY_FENCE` times the interquartile range are rejected as outliers.
+const TUKEY_FENCE: f64 = 1.5;
+
+/// Outlier rejection needs a few samples to estimate the quartiles.
+const MIN_SAMPLES_FOR_REJECTION: usize = 4;
+
+/// Statistics of the samples of a benched solution part. All durations are in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub std_dev: f64,
+    pub p90: f64,
+    pub p99: f64,
+    /// Number of samples the statistics are computed from.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Compute statistics from a set of samples, rejecting outliers with Tukey's fences.
+    /// Returns `None` if there are no samples.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_durations(durations: &[Duration]) -> Option<Self> {
+        if durations.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<f64> = durations.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let total = sorted.len();
+        let kept = reject_outliers(&sorted);
+
+        let len = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / len;
+        let variance = if kept.len() > 1 {
+            kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (len - 1.0)
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            mean,
+            median: percentile(kept, 50.0),
+            min: kept[0],
+            max: kept[kept.len() - 1],
+            std_dev: variance.sqrt(),
+            p90: percentile(kept, 90.0),
+            p99: percentile(kept, 99.0),
+            samples: kept.len() as u128,
+            outliers: (total - kept.len()) as u128,
+        })
+    }
+
+    /// Statistics for a single measurement, e.g. one that was stored without statistics.
+    pub fn from_single(nanos: f64) -> Self {
+        Self {
+            mean: nanos,
+            median: nanos,
+            min: nanos,
+            max: nanos,
+            std_dev: 0.0,
+            p90: nanos,
+            p99: nanos,
+            samples: 1,
+            outliers: 0,
+        }
+    }
+}
+
+/// Formats a duration in nanoseconds the same way `Debug` formats a [`Duration`], e.g. `1.5ms`.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+/// Computes the `p`-th percentile of sorted values, interpolating between the closest ranks.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+/// Returns the slice of sorted values that lies within Tukey's fences.
+fn reject_outliers(sorted: &[f64]) -> &[f64] {
+    if sorted.len() < MIN_SAMPLES_FOR_REJECTION {
+        return sorted;
+    }
+
+    let q1 = percentile(sorted, 25.0);
+    let q3 = percentile(sorted, 75.0);
+    let iqr = q3 - q1;
+    let (low, high) = (q1 - TUKEY_FENCE * iqr, q3 + TUKEY_FENCE * iqr);
+
+    let start = sorted.partition_point(|x| *x < low);
+    let end = sorted.partition_point(|x| *x <= high);
+    &sorted[start..end]
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("p90".into(), JsonValue::Number(value.p90));
+        map.insert("p99".into(), JsonValue::Number(value.p99));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            std_dev: number("std_dev")?,
+            p90: number("p90")?,
+            p99: number("p99")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_nanos, Stats};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(Stats::from_durations(&[]), None);
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = Stats::from_durations(&nanos(&[50, 10, 40, 20, 30])).unwrap();
+        assert_eq!(stats.mean, 30.0);
+        assert_eq!(stats.median, 30.0);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 50.0);
+        assert_eq!(stats.p90, 46.0);
+        assert!((stats.std_dev - 15.811_388).abs() < 1e-6);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_durations(&nanos(&[10, 11, 10, 12, 11, 10, 500])).unwrap();
+        assert_eq!(stats.max, 12.0);
+        assert_eq!(stats.samples, 6);
+        assert_eq!(stats.outliers, 1);
+    }
+
+    #[test]
+    fn formats_nanos() {
+        assert_eq!(format_nanos(74.13), "74.0ns");
+        assert_eq!(format_nanos(1_500_000.0), "1.5ms");
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = Stats::from_durations(&nanos(&[50, 10, 40, 20, 30])).unwrap();
+        let json = tinyjson::JsonValue::from(&stats);
+        assert_eq!(Stats::try_from(&json).unwrap(), stats);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_durations(&nanos(&[42])).unwrap();
+        assert_eq!(stats, Stats::from_single(42.0));
+    }
+}
@@ -0,0 +1,387 @@
+/// Local history of submitted answers, used to avoid submitting answers that are known to be wrong.
+use std::{collections::HashMap, fmt::Display, fs, io::Error, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::PuzzleId;
+
+/// The verdict of the server on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubmissionVerdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was submitted too soon after a previous one and was not checked.
+    TooSoon,
+    /// The part is solved already and the answer was not checked.
+    AlreadySolved,
+}
+
+impl SubmissionVerdict {
+    /// Read the verdict from the message the server responds with.
+    pub fn from_message(message: &str) -> Option<Self> {
+        if message.contains("That's the right answer") {
+            Some(SubmissionVerdict::Correct)
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Some(SubmissionVerdict::TooHigh)
+            } else if message.contains("your answer is too low") {
+                Some(SubmissionVerdict::TooLow)
+            } else {
+                Some(SubmissionVerdict::Wrong)
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Some(SubmissionVerdict::TooSoon)
+        } else if message.contains("You don't seem to be solving the right level") {
+            Some(SubmissionVerdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the server checked the answer and rejected it.
+    pub fn is_rejection(self) -> bool {
+        matches!(
+            self,
+            SubmissionVerdict::Wrong | SubmissionVerdict::TooHigh | SubmissionVerdict::TooLow
+        )
+    }
+}
+
+impl Display for SubmissionVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SubmissionVerdict::Correct => "correct",
+            SubmissionVerdict::Wrong => "wrong",
+            SubmissionVerdict::TooHigh => "too_high",
+            SubmissionVerdict::TooLow => "too_low",
+            SubmissionVerdict::TooSoon => "too_soon",
+            SubmissionVerdict::AlreadySolved => "already_solved",
+        })
+    }
+}
+
+impl FromStr for SubmissionVerdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(SubmissionVerdict::Correct),
+            "wrong" => Ok(SubmissionVerdict::Wrong),
+            "too_high" => Ok(SubmissionVerdict::TooHigh),
+            "too_low" => Ok(SubmissionVerdict::TooLow),
+            "too_soon" => Ok(SubmissionVerdict::TooSoon),
+            "already_solved" => Ok(SubmissionVerdict::AlreadySolved),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The reason an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was solved already.
+    AlreadyCorrect(String),
+    /// The answer was submitted before and rejected.
+    AlreadyRejected(SubmissionVerdict),
+    /// The answer is not a positive number and likely the result of a bug.
+    NotPositive,
+    /// The answer is not below an answer that was too high.
+    NotBelow(i128),
+    /// The answer is not above an answer that was too low.
+    NotAbove(i128),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect(answer) => {
+                write!(f, "the part was already solved with `{answer}`.")
+            }
+            Refusal::AlreadyRejected(verdict) => {
+                write!(f, "the answer was already submitted ({verdict}).")
+            }
+            Refusal::NotPositive => write!(f, "the answer is not a positive number."),
+            Refusal::NotBelow(high) => write!(f, "`{high}` was too high already."),
+            Refusal::NotAbove(low) => write!(f, "`{low}` was too low already."),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: SubmissionVerdict,
+}
+
+/// The answers submitted for a puzzle, stored in `data/{year}/submissions/{day}.json`.
+#[derive(Clone, Debug)]
+pub struct SubmissionLog {
+    pub puzzle: PuzzleId,
+    pub data: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    fn file_path(puzzle: PuzzleId) -> String {
+        puzzle.data_path("submissions", "json")
+    }
+
+    /// Rehydrate the log of a puzzle from its JSON file. If not present, returns an empty log.
+    pub fn read_from_file(puzzle: PuzzleId) -> Self {
+        let data = fs::read_to_string(Self::file_path(puzzle))
+            .map_err(|x| x.to_string())
+            .and_then(parse_submissions)
+            .unwrap_or_default();
+
+        Self { puzzle, data }
+    }
+
+    /// Dehydrate the log to its JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let path = Self::file_path(self.puzzle);
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    pub fn record(&mut self, part: u8, answer: &str, verdict: SubmissionVerdict) {
+        self.data.push(Submission {
+            part,
+            answer: answer.into(),
+            verdict,
+        });
+    }
+
+    /// Check an answer against earlier submissions of the same part before submitting it.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Refusal> {
+        let submissions = self.data.iter().filter(|s| s.part == part);
+
+        let mut lowest_too_high: Option<i128> = None;
+        let mut highest_too_low: Option<i128> = None;
+
+        for submission in submissions {
+            if submission.verdict == SubmissionVerdict::Correct {
+                return Err(Refusal::AlreadyCorrect(submission.answer.clone()));
+            }
+
+            if submission.answer == answer && submission.verdict.is_rejection() {
+                return Err(Refusal::AlreadyRejected(submission.verdict));
+            }
+
+            let Ok(value) = submission.answer.parse::<i128>() else {
+                continue;
+            };
+
+            match submission.verdict {
+                SubmissionVerdict::TooHigh => {
+                    lowest_too_high = Some(lowest_too_high.map_or(value, |x| x.min(value)));
+                }
+                SubmissionVerdict::TooLow => {
+                    highest_too_low = Some(highest_too_low.map_or(value, |x| x.max(value)));
+                }
+                _ => {}
+            }
+        }
+
+        if let Ok(value) = answer.trim().parse::<i128>() {
+            if value <= 0 {
+                return Err(Refusal::NotPositive);
+            }
+            if let Some(high) = lowest_too_high.filter(|high| value >= *high) {
+                return Err(Refusal::NotBelow(high));
+            }
+            if let Some(low) = highest_too_low.filter(|low| value <= *low) {
+                return Err(Refusal::NotAbove(low));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&SubmissionLog> for JsonValue {
+    fn from(value: &SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+fn parse_submissions(value: String) -> Result<Vec<Submission>, String> {
+    let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+    json.get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?
+        .get("data")
+        .ok_or("expected JSON document to have key `data`.")?
+        .get::<Vec<JsonValue>>()
+        .ok_or("expected `json.data` to be an array.")?
+        .iter()
+        .map(Submission::try_from)
+        .collect()
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?
+            .clone();
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Submission {
+            part,
+            answer,
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{parse_submissions, Refusal, SubmissionLog, SubmissionVerdict};
+    use crate::puzzle;
+
+    fn log(submissions: &[(u8, &str, SubmissionVerdict)]) -> SubmissionLog {
+        let mut log = SubmissionLog {
+            puzzle: puzzle!(2024, 1),
+            data: vec![],
+        };
+        for (part, answer, verdict) in submissions {
+            log.record(*part, answer, *verdict);
+        }
+        log
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            SubmissionVerdict::from_message(
+                "That's the right answer! You are one gold star closer."
+            ),
+            Some(SubmissionVerdict::Correct)
+        );
+        assert_eq!(
+            SubmissionVerdict::from_message("That's not the right answer; your answer is too low."),
+            Some(SubmissionVerdict::TooLow)
+        );
+        assert_eq!(
+            SubmissionVerdict::from_message("That's not the right answer. If you're stuck, ..."),
+            Some(SubmissionVerdict::Wrong)
+        );
+        assert_eq!(
+            SubmissionVerdict::from_message(
+                "You gave an answer too recently; You have 30s left to wait."
+            ),
+            Some(SubmissionVerdict::TooSoon)
+        );
+        assert_eq!(SubmissionVerdict::from_message("Hello"), None);
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let log = log(&[
+            (1, "abc", SubmissionVerdict::Wrong),
+            (1, "def", SubmissionVerdict::TooSoon),
+        ]);
+        assert_eq!(
+            log.check(1, "abc"),
+            Err(Refusal::AlreadyRejected(SubmissionVerdict::Wrong))
+        );
+        assert_eq!(log.check(1, "def"), Ok(()));
+        assert_eq!(log.check(2, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let log = log(&[
+            (1, "100", SubmissionVerdict::TooHigh),
+            (1, "80", SubmissionVerdict::TooHigh),
+            (1, "20", SubmissionVerdict::TooLow),
+        ]);
+        assert_eq!(log.check(1, "50"), Ok(()));
+        assert_eq!(log.check(1, "90"), Err(Refusal::NotBelow(80)));
+        assert_eq!(log.check(1, "85"), Err(Refusal::NotBelow(80)));
+        assert_eq!(log.check(1, "10"), Err(Refusal::NotAbove(20)));
+    }
+
+    #[test]
+    fn refuses_non_positive_answers() {
+        let log = log(&[]);
+        assert_eq!(log.check(1, "0"), Err(Refusal::NotPositive));
+        assert_eq!(log.check(1, "-12"), Err(Refusal::NotPositive));
+        assert_eq!(log.check(1, "12"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let log = log(&[(2, "12", SubmissionVerdict::Correct)]);
+        assert_eq!(
+            log.check(2, "13"),
+            Err(Refusal::AlreadyCorrect("12".into()))
+        );
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let log = log(&[
+            (1, "100", SubmissionVerdict::TooHigh),
+            (2, "x", SubmissionVerdict::Correct),
+        ]);
+        let json = JsonValue::from(&log).stringify().unwrap();
+        assert_eq!(parse_submissions(json).unwrap(), log.data);
+    }
+}
@@ -1,17 +1,27 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::memory::Memory;
+use crate::template::stats::Stats;
+use crate::template::{Day, PuzzleId, Year};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
-/// Represents benchmark times for a single day.
+/// Represents benchmark times for a single puzzle.
 #[derive(Clone, Debug)]
 pub struct Timing {
-    pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub puzzle: PuzzleId,
+    /// The parse phase, if the solution parses its input once for both parts.
+    pub parse: Option<Stats>,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
     pub total_nanos: f64,
+    /// Parts that returned an error.
+    pub failed: Vec<u8>,
+    /// The heap usage of the parse phase, if measured with `cargo time --memory`.
+    pub parse_memory: Option<Memory>,
+    /// The heap usage of part 1 and 2, if measured with `cargo time --memory`.
+    pub memory: [Option<Memory>; 2],
 }
 
 /// Represents benchmark times for a set of days.
@@ -46,12 +56,27 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| t.puzzle == timing.puzzle) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.puzzle);
+        Timings { data }
+    }
+
+    /// Copy the heap usage of `new` into the timings of the same puzzles, keeping their durations.
+    /// Puzzles without timings are skipped, as there is nothing to attach the heap usage to.
+    pub fn merge_memory(&self, new: &Self) -> Self {
+        let mut data = self.data.clone();
+
+        for timing in &mut data {
+            if let Some(measured) = new.data.iter().find(|t| t.puzzle == timing.puzzle) {
+                timing.parse_memory = measured.parse_memory;
+                timing.memory = measured.memory;
+            }
+        }
+
         Timings { data }
     }
 
@@ -60,10 +85,10 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    pub fn is_day_complete(&self, puzzle: PuzzleId) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.puzzle == puzzle && t.part_1.is_some() && t.part_2.is_some())
     }
 }
 
@@ -111,11 +136,26 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "year".into(),
+            JsonValue::String(value.puzzle.year().to_string()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::String(value.puzzle.day().to_string()),
+        );
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        map.insert(
+            "parse".into(),
+            match &value.parse {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        let part_1 = value.part_1.as_ref().map(JsonValue::from);
+        let part_2 = value.part_2.as_ref().map(JsonValue::from);
 
         map.insert(
             "part_1".into(),
@@ -133,6 +173,32 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "failed".into(),
+            JsonValue::Array(
+                value
+                    .failed
+                    .iter()
+                    .map(|part| JsonValue::Number((*part).into()))
+                    .collect(),
+            ),
+        );
+
+        if let Some(memory) = &value.parse_memory {
+            map.insert("parse_memory".into(), JsonValue::from(memory));
+        }
+
+        map.insert(
+            "memory".into(),
+            JsonValue::Array(
+                value
+                    .memory
+                    .iter()
+                    .map(|memory| memory.as_ref().map_or(JsonValue::Null, JsonValue::from))
+                    .collect(),
+            ),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -145,84 +211,224 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
+        // NOTE: timings stored before multi-year support have no year, assume the default year.
+        let year = match json.get("year") {
+            Some(v) => v
+                .get::<String>()
+                .and_then(|year| Year::from_str(year).ok())
+                .ok_or("Expected timing.year to be a Year struct.")?,
+            None => Year::from_env().ok_or("Expected timing.year to be present.")?,
+        };
+
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        let puzzle = PuzzleId::new(year, day).ok_or("Expected timing.day to be part of year.")?;
+
+        // NOTE: timings stored before the parse phase was timed have no `parse` key.
+        let parse = if json.contains_key("parse") {
+            parse_part(json, "parse")?
+        } else {
+            None
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let part_1 = parse_part(json, "part_1")?;
+        let part_2 = parse_part(json, "part_2")?;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings stored before failures were tracked have no `failed` key.
+        let failed = match json.get("failed") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .and_then(|parts| {
+                    parts
+                        .iter()
+                        .map(|part| part.get::<f64>().map(|part| *part as u8))
+                        .collect::<Option<Vec<u8>>>()
+                })
+                .ok_or("Expected timing.failed to be an array of numbers.")?,
+        };
+
+        // NOTE: timings stored without `--memory` have no `memory` key.
+        let memory = match json.get("memory") {
+            None => [None, None],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .and_then(|parts| {
+                    let parts = parts
+                        .iter()
+                        .map(|part| match part {
+                            JsonValue::Null => Some(None),
+                            part => Memory::try_from(part).ok().map(Some),
+                        })
+                        .collect::<Option<Vec<_>>>()?;
+                    parts.try_into().ok()
+                })
+                .ok_or("Expected timing.memory to be an array of two memory objects or nulls.")?,
+        };
+
+        let parse_memory = json.get("parse_memory").map(Memory::try_from).transpose()?;
+
         Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            puzzle,
+            parse,
+            part_1,
+            part_2,
             total_nanos,
+            failed,
+            parse_memory,
+            memory,
         })
     }
 }
 
+/// Parses the timing of a part. Timings stored before statistics were collected are a formatted
+/// duration string and are read as a single sample.
+fn parse_part(json: &HashMap<String, JsonValue>, key: &str) -> Result<Option<Stats>, String> {
+    match json.get(key) {
+        Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::String(s)) => parse_duration(s)
+            .map(|nanos| Some(Stats::from_single(nanos)))
+            .ok_or(format!("Expected timing.{key} to be a duration.")),
+        Some(v @ JsonValue::Object(_)) => Stats::try_from(v).map(Some),
+        _ => Err(format!(
+            "Expected timing.{key} to be null, string or object."
+        )),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parses a duration formatted by `Debug` into nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use crate::{puzzle, template::stats::Stats};
 
     use super::{Timing, Timings};
 
+    fn ms(millis: f64) -> Stats {
+        Stats::from_single(millis * 1_000_000_f64)
+    }
+
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    puzzle: puzzle!(2024, 1),
+                    parse: None,
+                    part_1: Some(ms(10.0)),
+                    part_2: Some(ms(20.0)),
                     total_nanos: 3e+10,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    puzzle: puzzle!(2024, 2),
+                    parse: None,
+                    part_1: Some(ms(30.0)),
+                    part_2: Some(ms(40.0)),
                     total_nanos: 7e+10,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
+                    puzzle: puzzle!(2024, 4),
+                    parse: None,
+                    part_1: Some(ms(40.0)),
                     part_2: None,
                     total_nanos: 4e+10,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use tinyjson::JsonValue;
+
+        use crate::{
+            puzzle,
+            template::{memory::Memory, timings::Timings},
+        };
+
+        use super::{get_mock_timings, ms};
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "year": "2024", "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.puzzle, puzzle!(2024, 1));
+            assert_eq!(timing.part_1, Some(ms(1.0)));
             assert_eq!(timing.part_2, None);
+            assert_eq!(timing.parse, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_statistics() {
+            let json = JsonValue::from(get_mock_timings()).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 3);
+            assert_eq!(timings.data[0].part_1, Some(ms(10.0)));
+            assert_eq!(timings.data[2].part_2, None);
+        }
+
+        #[test]
+        fn handles_json_parse_phase() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parse = Some(ms(5.0));
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].parse, Some(ms(5.0)));
+            assert_eq!(timings.data[1].parse, None);
+        }
+
+        #[test]
+        fn handles_json_memory() {
+            let mut timings = get_mock_timings();
+            timings.data[0].memory[1] = Some(Memory {
+                total_bytes: 2048,
+                peak_bytes: 1024,
+                allocations: 3,
+            });
+            timings.data[0].parse_memory = timings.data[0].memory[1];
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].memory[0], None);
+            assert_eq!(timings.data[0].memory[1].unwrap().peak_bytes, 1024);
+            assert_eq!(timings.data[0].parse_memory.unwrap().allocations, 3);
+            assert_eq!(timings.data[1].memory, [None, None]);
+            assert_eq!(timings.data[1].parse_memory, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -270,78 +476,97 @@ mod tests {
 
     mod is_day_complete {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
+        use super::ms;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    puzzle: puzzle!(2024, 1),
+                    parse: None,
+                    part_1: Some(ms(1.0)),
+                    part_2: Some(ms(2.0)),
                     total_nanos: 3_000_000_000_f64,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(puzzle!(2024, 1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
+                    puzzle: puzzle!(2024, 1),
+                    parse: None,
+                    part_1: Some(ms(1.0)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(2024, 1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    puzzle: puzzle!(2024, 1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(puzzle!(2024, 1)), false);
         }
     }
 
     mod merge {
         use crate::{
-            day,
+            puzzle,
+            template::memory::Memory,
             template::timings::{Timing, Timings},
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_timings, ms};
 
         #[test]
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
+                    puzzle: puzzle!(2024, 3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 }],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].puzzle, puzzle!(2024, 1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2024, 2));
+            assert_eq!(merged.data[2].puzzle, puzzle!(2024, 3));
+            assert_eq!(merged.data[3].puzzle, puzzle!(2024, 4));
         }
 
         #[test]
@@ -350,19 +575,23 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
+                    puzzle: puzzle!(2024, 2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failed: vec![],
+                    parse_memory: None,
+                    memory: [None, None],
                 }],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].puzzle, puzzle!(2024, 1));
+            assert_eq!(merged.data[1].puzzle, puzzle!(2024, 2));
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].puzzle, puzzle!(2024, 4));
         }
 
         #[test]
@@ -380,5 +609,26 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn merges_memory_only() {
+            let memory = Memory {
+                total_bytes: 2048,
+                peak_bytes: 1024,
+                allocations: 3,
+            };
+            let mut measured = get_mock_timings();
+            measured.data.truncate(1);
+            measured.data[0].part_1 = Some(ms(500.0));
+            measured.data[0].parse_memory = Some(memory);
+            measured.data[0].memory = [Some(memory), None];
+
+            let merged = get_mock_timings().merge_memory(&measured);
+            assert_eq!(merged.data.len(), 3);
+            assert_eq!(merged.data[0].part_1, Some(ms(10.0)));
+            assert_eq!(merged.data[0].parse_memory, Some(memory));
+            assert_eq!(merged.data[0].memory, [Some(memory), None]);
+            assert_eq!(merged.data[1].memory, [None, None]);
+        }
     }
 }
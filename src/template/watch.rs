@@ -0,0 +1,162 @@
+/// Re-runs a command whenever the files of a puzzle change, see `cargo solve <day> --watch`.
+/// Files are polled for changes, which needs no platform-specific file system events.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::{stdout, Write},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use regex::Regex;
+
+use crate::template::{PuzzleId, ANSI_ITALIC, ANSI_RESET};
+
+/// How often files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Editors often write a file several times when saving, changes within this delay are merged.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Returns the files that affect the solution of a puzzle: its module, the library modules it
+/// uses, its example and input files.
+pub fn watched_files(puzzle: PuzzleId) -> BTreeSet<PathBuf> {
+    let module = PathBuf::from(format!("src/bin/{puzzle}.rs"));
+    let mut files = BTreeSet::from([module.clone(), PathBuf::from("src/lib.rs")]);
+
+    if let Ok(source) = fs::read_to_string(&module) {
+        for name in library_modules(&source) {
+            files.insert(PathBuf::from(format!("src/{name}.rs")));
+            files.extend(rust_files(Path::new(&format!("src/{name}"))));
+        }
+    }
+
+    files.insert(PathBuf::from(puzzle.data_path("inputs", "txt")));
+    files.insert(PathBuf::from(puzzle.data_path("examples", "json")));
+
+    // examples are stored as `{day}.txt` or as `{day}-{n}.txt` if a puzzle has several.
+    let examples = format!("data/{}/examples", puzzle.year());
+    if let Ok(entries) = fs::read_dir(examples) {
+        let day = puzzle.day().to_string();
+        files.extend(entries.flatten().map(|e| e.path()).filter(|path| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| stem == day || stem.starts_with(&format!("{day}-")))
+        }));
+    }
+
+    files
+}
+
+/// Returns the names of the library modules a solution imports, e.g. `grid` for
+/// `use advent_of_code::grid::Grid;`.
+fn library_modules(source: &str) -> BTreeSet<String> {
+    let module = Regex::new(r"advent_of_code::(\w+)::").unwrap();
+    module
+        .captures_iter(source)
+        .map(|c| c[1].to_string())
+        .collect()
+}
+
+fn rust_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .flat_map(|path| {
+            if path.is_dir() {
+                rust_files(&path)
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                vec![path]
+            } else {
+                vec![]
+            }
+        })
+        .collect()
+}
+
+/// The modification time of every existing watched file.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+fn snapshot(puzzle: PuzzleId) -> Snapshot {
+    watched_files(puzzle)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Runs `cargo` with every set of arguments, then waits until a file of the puzzle changes and
+/// repeats. The output of the previous run is cleared. Runs until the process is interrupted.
+pub fn watch(puzzle: PuzzleId, commands: &[Vec<String>]) {
+    loop {
+        let before = snapshot(puzzle);
+
+        // clears the screen and moves the cursor to the top left corner.
+        print!("\x1b[2J\x1b[H");
+        let _ = stdout().flush();
+
+        for (i, args) in commands.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            let status = Command::new("cargo")
+                .args(args)
+                .stdout(Stdio::inherit())
+                .stderr(Stdio::inherit())
+                .status();
+            if let Err(e) = status {
+                eprintln!("Failed to run cargo: {e}");
+            }
+        }
+
+        println!();
+        println!(
+            "{ANSI_ITALIC}Watching {} file(s) of {puzzle} for changes, press Ctrl+C to stop.{ANSI_RESET}",
+            before.len()
+        );
+
+        let mut current = snapshot(puzzle);
+        while current == before {
+            thread::sleep(POLL_INTERVAL);
+            current = snapshot(puzzle);
+        }
+        thread::sleep(DEBOUNCE);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::library_modules;
+
+    #[test]
+    fn finds_library_modules() {
+        let source = "\
+advent_of_code::solution!(2024, 6);
+
+use advent_of_code::grid::{Direction, Grid, Point};
+use advent_of_code::simulate::find_cycle;
+
+let result = part_one(&advent_of_code::template::read_file(\"examples\", PUZZLE));
+";
+        assert_eq!(
+            library_modules(source),
+            BTreeSet::from([
+                "grid".to_string(),
+                "simulate".to_string(),
+                "template".to_string()
+            ])
+        );
+    }
+}
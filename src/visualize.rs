@@ -0,0 +1,523 @@
+/// Frames that solutions record while running, e.g. the steps of a simulation. Recording is only
+/// enabled when solving a puzzle with `--visualize`; the frames of a part are played back in the
+/// terminal or exported once the part finished.
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display},
+    io::{stdout, Write},
+    process,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::grid::{Grid, Point};
+use crate::template::PuzzleId;
+
+/// The size of a cell in exported images, in pixels.
+#[cfg(feature = "visualize-export")]
+const PIXELS_PER_CELL: usize = 4;
+
+pub const DEFAULT_FPS: u32 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    pub fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::Black => (0, 0, 0),
+            Color::Red => (205, 49, 49),
+            Color::Green => (13, 188, 121),
+            Color::Yellow => (229, 229, 16),
+            Color::Blue => (36, 114, 200),
+            Color::Magenta => (188, 63, 188),
+            Color::Cyan => (17, 168, 205),
+            Color::White => (229, 229, 229),
+            Color::Gray => (102, 102, 102),
+            Color::Rgb(r, g, b) => (r, g, b),
+        }
+    }
+}
+
+/// A character of a frame along with its colors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Option<Color>,
+    pub background: Option<Color>,
+}
+
+impl Cell {
+    pub fn new(symbol: char) -> Self {
+        Self {
+            symbol,
+            color: None,
+            background: None,
+        }
+    }
+
+    pub fn color(self, color: Color) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    pub fn background(self, background: Color) -> Self {
+        Self {
+            background: Some(background),
+            ..self
+        }
+    }
+
+    /// The color of the cell in exported images. Cells without colors are drawn white, unless
+    /// they are empty (` ` or `.`).
+    pub fn pixel(&self) -> Color {
+        self.background.or(self.color).unwrap_or(match self.symbol {
+            ' ' | '.' => Color::Black,
+            _ => Color::White,
+        })
+    }
+}
+
+impl From<char> for Cell {
+    fn from(symbol: char) -> Self {
+        Cell::new(symbol)
+    }
+}
+
+/// A single image of an animation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<Cell>,
+}
+
+impl Frame {
+    /// Draws a grid, `cell` returns the cell to draw for each point.
+    pub fn from_grid<T>(grid: &Grid<T>, mut cell: impl FnMut(Point, &T) -> Cell) -> Self {
+        Self {
+            cells: Grid::from_fn(grid.width(), grid.height(), |point| {
+                cell(point, &grid[point])
+            }),
+        }
+    }
+
+    /// Draws text without colors, lines shorter than the longest one are padded with spaces.
+    pub fn from_text(text: &str) -> Self {
+        let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        let width = lines.iter().map(Vec::len).max().unwrap_or_default();
+
+        Self {
+            cells: Grid::from_fn(width, lines.len(), |point| {
+                Cell::new(lines[point.y].get(point.x).copied().unwrap_or(' '))
+            }),
+        }
+    }
+
+    /// Draws the `Display` output of a value, e.g. of a struct that already prints itself.
+    pub fn from_display(value: &impl Display) -> Self {
+        Self::from_text(&value.to_string())
+    }
+
+    pub fn cells(&self) -> &Grid<Cell> {
+        &self.cells
+    }
+
+    /// Replaces the cell at a point, points outside the frame are ignored.
+    pub fn set(&mut self, point: Point, cell: Cell) {
+        if let Some(current) = self.cells.get_mut(point) {
+            *current = cell;
+        }
+    }
+
+    /// Colors the cell at a point, points outside the frame are ignored.
+    pub fn highlight(&mut self, point: Point, color: Color) {
+        if let Some(cell) = self.cells.get_mut(point) {
+            cell.color = Some(color);
+        }
+    }
+
+    /// Returns the frame as text with ANSI colors.
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+        for row in self.cells.rows() {
+            for cell in row {
+                if let Some(color) = cell.color {
+                    let (r, g, b) = color.rgb();
+                    ansi.push_str(&format!("\x1b[38;2;{r};{g};{b}m"));
+                }
+                if let Some(background) = cell.background {
+                    let (r, g, b) = background.rgb();
+                    ansi.push_str(&format!("\x1b[48;2;{r};{g};{b}m"));
+                }
+                ansi.push(cell.symbol);
+                if cell.color.is_some() || cell.background.is_some() {
+                    ansi.push_str("\x1b[0m");
+                }
+            }
+            ansi.push('\n');
+        }
+        ansi
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Output {
+    /// Plays the frames back in the terminal.
+    #[default]
+    Terminal,
+    /// Exports the frames to `data/{year}/visualizations/{day}-{part}.gif`.
+    Gif,
+    /// Exports the frames to `data/{year}/visualizations/{day}-{part}/{frame}.png`.
+    Png,
+}
+
+impl Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Output::Terminal => "terminal",
+            Output::Gif => "gif",
+            Output::Png => "png",
+        })
+    }
+}
+
+impl FromStr for Output {
+    type Err = OutputFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "terminal" => Ok(Output::Terminal),
+            "gif" => Ok(Output::Gif),
+            "png" => Ok(Output::Png),
+            _ => Err(OutputFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`Output`].
+#[derive(Debug)]
+pub struct OutputFromStrError;
+
+impl Error for OutputFromStrError {}
+
+impl Display for OutputFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("expecting one of `terminal`, `gif` or `png`")
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VisualizeOptions {
+    pub fps: u32,
+    pub output: Output,
+}
+
+impl Default for VisualizeOptions {
+    fn default() -> Self {
+        Self {
+            fps: DEFAULT_FPS,
+            output: Output::default(),
+        }
+    }
+}
+
+impl VisualizeOptions {
+    /// Returns the arguments that enable these options in a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--visualize".to_string(),
+            "--fps".to_string(),
+            self.fps.to_string(),
+            "--export".to_string(),
+            self.output.to_string(),
+        ]
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static OPTIONS: OnceLock<VisualizeOptions> = OnceLock::new();
+static FRAMES: Mutex<Vec<Frame>> = Mutex::new(vec![]);
+
+/// Whether frames are recorded, e.g. to skip drawing them otherwise.
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Starts recording frames.
+pub fn enable(options: VisualizeOptions) {
+    if OPTIONS.set(options).is_ok() {
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Records a frame, if recording is enabled.
+pub fn push(frame: Frame) {
+    if is_enabled() {
+        FRAMES.lock().unwrap().push(frame);
+    }
+}
+
+/// Records the frame returned by `frame`, which is only called if recording is enabled.
+pub fn push_with(frame: impl FnOnce() -> Frame) {
+    if is_enabled() {
+        push(frame());
+    }
+}
+
+/// Enables recording if the solution was run with `--visualize`, e.g. `--visualize --fps 30` or
+/// `--visualize --export gif`.
+pub fn init_from_args() {
+    let args: Vec<String> = env::args().collect();
+    if !args.iter().any(|x| x == "--visualize") {
+        return;
+    }
+
+    let value = |flag: &str| {
+        args.iter()
+            .position(|x| x == flag)
+            .map(|index| args.get(index + 1).map(String::as_str).unwrap_or_default())
+    };
+
+    let fps = match value("--fps").map(str::parse) {
+        None => DEFAULT_FPS,
+        Some(Ok(fps)) if fps > 0 => fps,
+        Some(_) => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 6 --visualize --fps 30");
+            process::exit(1);
+        }
+    };
+
+    let output = match value("--export").map(str::parse) {
+        None => Output::Terminal,
+        Some(Ok(output)) => output,
+        Some(Err(e)) => {
+            eprintln!("Unexpected command-line input for --export: {e}");
+            process::exit(1);
+        }
+    };
+
+    enable(VisualizeOptions { fps, output });
+}
+
+/// Plays back or exports the frames recorded while a part ran.
+pub fn finish(puzzle: PuzzleId, part: u8) {
+    let Some(options) = OPTIONS.get().filter(|_| is_enabled()) else {
+        return;
+    };
+    let frames = std::mem::take(&mut *FRAMES.lock().unwrap());
+    if frames.is_empty() {
+        return;
+    }
+
+    match options.output {
+        Output::Terminal => play(&frames, options.fps),
+        Output::Gif | Output::Png => export(&frames, puzzle, part, options),
+    }
+}
+
+fn play(frames: &[Frame], fps: u32) {
+    let delay = Duration::from_secs(1) / fps;
+    let mut stdout = stdout();
+    let mut height = 0;
+
+    // redraws every frame in place of the previous one, so earlier output stays visible.
+    print!("\x1b[?25l");
+    for frame in frames {
+        if height > 0 {
+            print!("\x1b[{height}A\x1b[J");
+        }
+        print!("{}", frame.to_ansi());
+        let _ = stdout.flush();
+        height = frame.cells().height();
+        thread::sleep(delay);
+    }
+    print!("\x1b[?25h");
+    let _ = stdout.flush();
+}
+
+#[cfg(not(feature = "visualize-export"))]
+fn export(_: &[Frame], _: PuzzleId, _: u8, _: &VisualizeOptions) {
+    eprintln!("Exporting frames requires the `visualize-export` feature.");
+}
+
+#[cfg(feature = "visualize-export")]
+fn export(frames: &[Frame], puzzle: PuzzleId, part: u8, options: &VisualizeOptions) {
+    let path = format!(
+        "data/{}/visualizations/{}-{part}",
+        puzzle.year(),
+        puzzle.day()
+    );
+
+    let result = match options.output {
+        Output::Gif => export::gif(frames, &format!("{path}.gif"), options.fps),
+        _ => export::png(frames, &path),
+    };
+
+    match result {
+        Ok(path) => println!("Exported {} frames to \"{path}\"", frames.len()),
+        Err(e) => eprintln!("Failed to export frames: {e}"),
+    }
+}
+
+#[cfg(feature = "visualize-export")]
+mod export {
+    use std::{collections::HashMap, error::Error, fs, io::BufWriter, path::Path};
+
+    use super::{Color, Frame, PIXELS_PER_CELL};
+
+    fn create_parent(path: &str) -> Result<(), Box<dyn Error>> {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the colors of every pixel of a frame, row by row, padded to `width` and `height`.
+    fn pixels(frame: &Frame, width: usize, height: usize) -> Vec<Color> {
+        let cells = frame.cells();
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let point = crate::grid::Point::new(x / PIXELS_PER_CELL, y / PIXELS_PER_CELL);
+                pixels.push(cells.get(point).map_or(Color::Black, |cell| cell.pixel()));
+            }
+        }
+        pixels
+    }
+
+    pub fn gif(frames: &[Frame], path: &str, fps: u32) -> Result<String, Box<dyn Error>> {
+        create_parent(path)?;
+
+        let width = frames
+            .iter()
+            .map(|f| f.cells().width())
+            .max()
+            .unwrap_or_default();
+        let height = frames
+            .iter()
+            .map(|f| f.cells().height())
+            .max()
+            .unwrap_or_default();
+        let (width, height) = (width * PIXELS_PER_CELL, height * PIXELS_PER_CELL);
+
+        // a shared palette of every color used, gifs support up to 256 of them.
+        let mut palette: HashMap<Color, u8> = HashMap::new();
+        let mut indexed = vec![];
+        for frame in frames {
+            let mut pixels = vec![];
+            for color in self::pixels(frame, width, height) {
+                let next = palette.len();
+                let index = *palette
+                    .entry(color)
+                    .or_insert_with(|| u8::try_from(next).unwrap_or(u8::MAX));
+                pixels.push(index);
+            }
+            indexed.push(pixels);
+        }
+
+        let mut colors = vec![0; palette.len().max(2) * 3];
+        for (color, index) in &palette {
+            let (r, g, b) = color.rgb();
+            let index = usize::from(*index) * 3;
+            colors[index..index + 3].copy_from_slice(&[r, g, b]);
+        }
+
+        let file = BufWriter::new(fs::File::create(path)?);
+        let mut encoder =
+            ::gif::Encoder::new(file, u16::try_from(width)?, u16::try_from(height)?, &colors)?;
+        encoder.set_repeat(::gif::Repeat::Infinite)?;
+
+        // gif delays are in hundredths of a second.
+        let delay = u16::try_from((100 / fps).max(1))?;
+        for pixels in indexed {
+            let mut frame = ::gif::Frame::from_indexed_pixels(
+                u16::try_from(width)?,
+                u16::try_from(height)?,
+                pixels,
+                None,
+            );
+            frame.delay = delay;
+            encoder.write_frame(&frame)?;
+        }
+
+        Ok(path.to_string())
+    }
+
+    pub fn png(frames: &[Frame], directory: &str) -> Result<String, Box<dyn Error>> {
+        fs::create_dir_all(directory)?;
+
+        for (i, frame) in frames.iter().enumerate() {
+            let width = frame.cells().width() * PIXELS_PER_CELL;
+            let height = frame.cells().height() * PIXELS_PER_CELL;
+
+            let data: Vec<u8> = pixels(frame, width, height)
+                .into_iter()
+                .flat_map(|color| {
+                    let (r, g, b) = color.rgb();
+                    [r, g, b]
+                })
+                .collect();
+
+            let file = BufWriter::new(fs::File::create(format!("{directory}/{:04}.png", i + 1))?);
+            let mut encoder =
+                ::png::Encoder::new(file, u32::try_from(width)?, u32::try_from(height)?);
+            encoder.set_color(::png::ColorType::Rgb);
+            encoder.set_depth(::png::BitDepth::Eight);
+            encoder.write_header()?.write_image_data(&data)?;
+        }
+
+        Ok(directory.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cell, Color, Frame};
+    use crate::grid::{Grid, Point};
+
+    #[test]
+    fn draws_frames() {
+        let frame = Frame::from_text("#.\n#\n");
+        assert_eq!((frame.cells().width(), frame.cells().height()), (2, 2));
+        assert_eq!(frame.cells()[Point::new(1, 1)], Cell::new(' '));
+        assert_eq!(frame.to_ansi(), "#.\n# \n");
+
+        let grid: Grid<bool> = Grid::parse("#.", |c| c == '#').unwrap();
+        let mut frame = Frame::from_grid(&grid, |_, wall| {
+            if *wall {
+                Cell::new('#').color(Color::Red)
+            } else {
+                Cell::new('.')
+            }
+        });
+        assert_eq!(frame.to_ansi(), "\x1b[38;2;205;49;49m#\x1b[0m.\n");
+
+        frame.highlight(Point::new(1, 0), Color::Rgb(1, 2, 3));
+        frame.set(Point::new(5, 5), Cell::new('x'));
+        assert_eq!(frame.cells()[Point::new(1, 0)].pixel(), Color::Rgb(1, 2, 3));
+        assert_eq!(Cell::new('.').pixel(), Color::Black);
+        assert_eq!(Cell::new('#').pixel(), Color::White);
+    }
+}